@@ -1,18 +1,44 @@
 use sp_core::{Pair, Public, sr25519};
 use node_template_runtime::{
-	AccountId, BabeConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
+	AccountId, ExistentialDeposit, BabeConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
 	SudoConfig, SystemConfig, WASM_BINARY, Signature, SessionConfig, StakingConfig, StakerStatus,
-	opaque::SessionKeys, Balance
+	CouncilConfig, DemocracyConfig, ElectionsConfig, TechnicalCommitteeConfig,
+	ImOnlineConfig, AuthorityDiscoveryConfig, IndicesConfig, PriceOracleConfig, AccountIndex,
+	opaque::SessionKeys, Balance, DOLLARS, TOKEN_SYMBOL, TOKEN_DECIMALS, SS58_PREFIX,
 };
-// use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_babe::{AuthorityId as BabeId};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
-use sc_service::ChainType;
+use sc_service::{ChainType, Properties};
+use sc_telemetry::TelemetryEndpoints;
 use sp_runtime::{Perbill};
+use std::collections::BTreeMap;
+use crate::generate_spec::{parse_account, parse_validator};
 
-// The URL for the telemetry server.
-// const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+/// Genesis validators of the public staging testnet, each as
+/// `stash,controller,babe,grandpa,im_online,authority_discovery` SS58 public keys, like the
+/// `--validator` argument of `generate-spec`.
+///
+/// Empty until the staging validators have generated their keys; the preset refuses to start
+/// without them.
+const STAGING_VALIDATORS: &[&str] = &[];
+/// Sudo key of the staging testnet, as an SS58 public key. It is also the first council member.
+const STAGING_SUDO: &str = "";
+/// Bootnodes of the staging testnet, as multiaddresses ending with the peer ID of the node.
+const STAGING_BOOTNODES: &[&str] = &[];
+/// Telemetry server the staging nodes report to.
+const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
+/// Network protocol ID of the staging testnet, so that its nodes don't connect to other chains.
+const STAGING_PROTOCOL_ID: &str = "tmpl-staging";
+
+/// Raw chain spec of the mainnet.
+///
+/// The mainnet is loaded from a frozen raw spec, so that its genesis does not change with the
+/// runtime. `None` until the spec is generated from the keys of its genesis validators; see the
+/// README for how.
+const MAINNET_SPEC: Option<&[u8]> = None;
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
@@ -33,19 +59,47 @@ pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Amount bonded by every genesis validator and elected council member.
+pub const STASH: Balance = 100 * DOLLARS;
+/// Free balance given to every endowed account of the development chains.
+pub const ENDOWMENT: Balance = 10_000 * DOLLARS;
+
+/// Trading pairs registered with the price oracle on the development chains, so that their
+/// oracles can report without waiting for a council motion.
+pub const DEV_TRADING_PAIRS: [price_oracle::PairId; 2] = [0, 1];
+
+/// Keys of a genesis authority: BABE, GRANDPA, stash, controller, ImOnline and
+/// authority discovery, in that order.
+pub type AuthorityKeys = (BabeId, GrandpaId, AccountId, AccountId, ImOnlineId, AuthorityDiscoveryId);
+
 /// Generate an Babe authority key.
-pub fn authority_keys_from_seed(s: &str) -> (BabeId, GrandpaId, AccountId, AccountId) {
+pub fn authority_keys_from_seed(s: &str) -> AuthorityKeys {
 	(
 		get_from_seed::<BabeId>(s),
 		get_from_seed::<GrandpaId>(s),
 		get_account_id_from_seed::<sr25519::Public>(&format!("{}//stash", s)),
-		get_account_id_from_seed::<sr25519::Public>(s)
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<ImOnlineId>(s),
+		get_from_seed::<AuthorityDiscoveryId>(s),
 	)
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
+	// Initial Babe authorities
+	let initial_authorities = vec![
+		authority_keys_from_seed("Alice"),
+	];
+	// Pre-funded accounts
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+	].into_iter().map(|k| (k, ENDOWMENT)).collect::<Vec<_>>();
+	validate_genesis(&initial_authorities, &endowed_accounts)?;
+
 	Ok(ChainSpec::from_genesis(
 		// Name
 		"Development",
@@ -54,19 +108,11 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		ChainType::Development,
 		move || testnet_genesis(
 			wasm_binary,
-			// Initial Babe authorities
-			vec![
-				authority_keys_from_seed("Alice"),
-			],
+			initial_authorities.clone(),
 			// Sudo account
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			// Pre-funded accounts
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-			],
+			endowed_accounts.clone(),
+			DEV_TRADING_PAIRS.to_vec(),
 			true,
 		),
 		// Bootnodes
@@ -76,7 +122,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
-		None,
+		Some(chain_properties()),
 		// Extensions
 		None,
 	))
@@ -85,6 +131,28 @@ pub fn development_config() -> Result<ChainSpec, String> {
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
 
+	// Initial Babe authorities
+	let initial_authorities = vec![
+		authority_keys_from_seed("Alice"),
+		authority_keys_from_seed("Bob"),
+	];
+	// Pre-funded accounts
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+		get_account_id_from_seed::<sr25519::Public>("Dave"),
+		get_account_id_from_seed::<sr25519::Public>("Eve"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
+	].into_iter().map(|k| (k, ENDOWMENT)).collect::<Vec<_>>();
+	validate_genesis(&initial_authorities, &endowed_accounts)?;
+
 	Ok(ChainSpec::from_genesis(
 		// Name
 		"Local Testnet",
@@ -93,28 +161,11 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		ChainType::Local,
 		move || testnet_genesis(
 			wasm_binary,
-			// Initial Babe authorities
-			vec![
-				authority_keys_from_seed("Alice"),
-				authority_keys_from_seed("Bob"),
-			],
+			initial_authorities.clone(),
 			// Sudo account
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
-			// Pre-funded accounts
-			vec![
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
-				get_account_id_from_seed::<sr25519::Public>("Bob"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie"),
-				get_account_id_from_seed::<sr25519::Public>("Dave"),
-				get_account_id_from_seed::<sr25519::Public>("Eve"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-				get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-				get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-			],
+			endowed_accounts.clone(),
+			DEV_TRADING_PAIRS.to_vec(),
 			true,
 		),
 		// Bootnodes
@@ -124,21 +175,184 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Protocol ID
 		None,
 		// Properties
+		Some(chain_properties()),
+		// Extensions
 		None,
+	))
+}
+
+pub fn staging_testnet_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or("Staging wasm binary not available".to_string())?;
+
+	let initial_authorities = STAGING_VALIDATORS.iter()
+		.map(|v| parse_validator(v))
+		.collect::<Result<Vec<_>, _>>()?;
+	if initial_authorities.is_empty() {
+		return Err("The staging testnet has no genesis validators yet".into());
+	}
+	let root_key = parse_account(STAGING_SUDO)?;
+	// The sudo key comes first since the leading accounts form the initial council.
+	let endowed_accounts = Some(root_key.clone()).into_iter()
+		.chain(initial_authorities.iter().flat_map(|x| vec![x.2.clone(), x.3.clone()]))
+		.map(|k| (k, ENDOWMENT))
+		.collect::<Vec<_>>();
+	validate_genesis(&initial_authorities, &endowed_accounts)?;
+
+	let boot_nodes = STAGING_BOOTNODES.iter()
+		.map(|addr| addr.parse().map_err(|e| format!("Invalid bootnode `{}`: {}", addr, e)))
+		.collect::<Result<Vec<_>, _>>()?;
+	let telemetry = TelemetryEndpoints::new(vec![(STAGING_TELEMETRY_URL.to_string(), 0)])
+		.map_err(|e| format!("Invalid telemetry URL `{}`: {:?}", STAGING_TELEMETRY_URL, e))?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Staging Testnet",
+		// ID
+		"staging_testnet",
+		ChainType::Live,
+		move || testnet_genesis(
+			wasm_binary,
+			initial_authorities.clone(),
+			root_key.clone(),
+			endowed_accounts.clone(),
+			// Live chains register their pairs through the council.
+			vec![],
+			false,
+		),
+		// Bootnodes
+		boot_nodes,
+		// Telemetry
+		Some(telemetry),
+		// Protocol ID
+		Some(STAGING_PROTOCOL_ID),
+		// Properties
+		Some(chain_properties()),
 		// Extensions
 		None,
 	))
 }
 
+pub fn mainnet_config() -> Result<ChainSpec, String> {
+	frozen_spec("mainnet", MAINNET_SPEC)
+}
+
+fn frozen_spec(name: &str, spec: Option<&'static [u8]>) -> Result<ChainSpec, String> {
+	let spec = spec.ok_or_else(|| format!("The {} chain spec has not been published yet", name))?;
+	ChainSpec::from_json_bytes(spec)
+}
+
+/// Token metadata and address format shown by wallets and explorers.
+pub fn chain_properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58_PREFIX.into());
+	properties
+}
+
+/// Check that a set of genesis authorities can author and finalize blocks.
+///
+/// Every key type must be unique across authorities, stash and controller accounts must not be
+/// shared between validators, and both must be endowed so the stash can bond [`STASH`], itself no
+/// less than the existential deposit. The members of the initial council must be endowed with
+/// enough to stake [`STASH`] too. Catching these here avoids building a chain that panics during
+/// genesis or stalls right after block 1.
+pub fn validate_genesis(
+	initial_authorities: &[AuthorityKeys],
+	endowed_accounts: &[(AccountId, Balance)],
+) -> Result<(), String> {
+	if initial_authorities.is_empty() {
+		return Err("At least one genesis authority is required".into());
+	}
+	validate_bond(STASH)?;
+
+	ensure_unique("endowed account", endowed_accounts.iter().map(|x| &x.0))?;
+	if let Some((who, _)) = endowed_accounts.iter()
+		.find(|(_, balance)| *balance < ExistentialDeposit::get())
+	{
+		return Err(format!(
+			"Endowment of {} is below the existential deposit of {}", who, ExistentialDeposit::get(),
+		));
+	}
+	if let Some((who, _)) = initial_council(endowed_accounts).iter()
+		.find(|(_, balance)| *balance < STASH)
+	{
+		return Err(format!("Council member {} is not endowed with enough to stake {}", who, STASH));
+	}
+	let endowment = |who: &AccountId| endowed_accounts.iter()
+		.find(|(account, _)| account == who)
+		.map(|(_, balance)| *balance);
+
+	ensure_unique("BABE key", initial_authorities.iter().map(|x| &x.0))?;
+	ensure_unique("GRANDPA key", initial_authorities.iter().map(|x| &x.1))?;
+	ensure_unique("stash account", initial_authorities.iter().map(|x| &x.2))?;
+	ensure_unique("controller account", initial_authorities.iter().map(|x| &x.3))?;
+	ensure_unique("ImOnline key", initial_authorities.iter().map(|x| &x.4))?;
+	ensure_unique("authority discovery key", initial_authorities.iter().map(|x| &x.5))?;
+
+	for (i, (_, _, stash, controller, _, _)) in initial_authorities.iter().enumerate() {
+		if let Some(j) = initial_authorities.iter().position(|x| &x.2 == controller) {
+			if i != j {
+				return Err(format!(
+					"Controller of genesis authority #{} is the stash of authority #{}", i, j,
+				));
+			}
+		}
+		if endowment(stash).map_or(true, |balance| balance < STASH) {
+			return Err(format!(
+				"Stash {} of genesis authority #{} is not endowed with enough to bond {}", stash, i, STASH,
+			));
+		}
+		if endowment(controller).is_none() {
+			return Err(format!(
+				"Controller {} of genesis authority #{} is not endowed", controller, i,
+			));
+		}
+	}
+
+	Ok(())
+}
+
+/// Staking refuses bonds below the existential deposit, and genesis ignores the failure, which
+/// would leave the chain without validators.
+fn validate_bond(bond: Balance) -> Result<(), String> {
+	if bond < ExistentialDeposit::get() {
+		return Err(format!(
+			"Bond of {} is below the existential deposit of {}", bond, ExistentialDeposit::get(),
+		));
+	}
+	Ok(())
+}
+
+/// The first half of the endowed accounts form the initial council.
+fn initial_council(endowed_accounts: &[(AccountId, Balance)]) -> &[(AccountId, Balance)] {
+	&endowed_accounts[..(endowed_accounts.len() + 1) / 2]
+}
+
+fn ensure_unique<'a, T: Ord + 'a>(
+	what: &str,
+	keys: impl Iterator<Item = &'a T>,
+) -> Result<(), String> {
+	let mut seen = BTreeMap::new();
+	for (i, key) in keys.enumerate() {
+		if let Some(j) = seen.insert(key, i) {
+			return Err(format!("Entries #{} and #{} share the same {}", j, i, what));
+		}
+	}
+	Ok(())
+}
+
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
+///
+/// The authorities and endowments must have passed [`validate_genesis`].
+pub fn testnet_genesis(
 	wasm_binary: &[u8],
-	initial_authorities: Vec<(BabeId, GrandpaId, AccountId,AccountId)>,
+	initial_authorities: Vec<AuthorityKeys>,
 	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	trading_pairs: Vec<price_oracle::PairId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
-	const STASH: Balance = 100;
 	GenesisConfig {
 		frame_system: Some(SystemConfig {
 			// Add Wasm runtime to storage.
@@ -146,26 +360,61 @@ fn testnet_genesis(
 			changes_trie_config: Default::default(),
 		}),
 		pallet_balances: Some(BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k|(k, 1 << 60)).collect(),
+			balances: endowed_accounts.clone(),
+		}),
+		// Endowed accounts can be addressed by their position in the list.
+		pallet_indices: Some(IndicesConfig {
+			indices: endowed_accounts.iter()
+				.enumerate()
+				.map(|(index, (who, _))| (index as AccountIndex, who.clone()))
+				.collect(),
 		}),
+		// BABE and GRANDPA authorities are left empty on purpose: the session pallet initializes
+		// both from `pallet_session` keys below, and setting them here as well would make the
+		// genesis build panic with "Authorities are already initialized!".
 		pallet_babe: Some(BabeConfig {
-			// authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
 			authorities: vec![],
 		}),
 		pallet_grandpa: Some(GrandpaConfig {
-			// authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
 			authorities: vec![],
 		}),
+		pallet_treasury: Some(Default::default()),
+		pallet_democracy: Some(DemocracyConfig::default()),
+		pallet_elections_phragmen: Some(ElectionsConfig {
+			members: initial_council(&endowed_accounts).iter()
+				.map(|(member, _)| (member.clone(), STASH))
+				.collect(),
+		}),
+		pallet_collective_Instance1: Some(CouncilConfig::default()),
+		pallet_collective_Instance2: Some(TechnicalCommitteeConfig {
+			members: initial_council(&endowed_accounts).iter()
+				.map(|(member, _)| member.clone())
+				.collect(),
+			phantom: Default::default(),
+		}),
+		pallet_membership_Instance1: Some(Default::default()),
+		pallet_im_online: Some(ImOnlineConfig {
+			keys: vec![],
+		}),
+		pallet_authority_discovery: Some(AuthorityDiscoveryConfig {
+			keys: vec![],
+		}),
 		pallet_sudo: Some(SudoConfig {
 			// Assign network admin rights.
-			key: root_key,
+			key: root_key.clone(),
+		}),
+		// The sudo key reports prices until the council appoints oracles.
+		price_oracle: Some(PriceOracleConfig {
+			oracles: vec![root_key],
+			trading_pairs,
 		}),
 		pallet_session: Some(SessionConfig {
 			keys: initial_authorities.iter().map(|x| {
 				(x.2.clone(), x.2.clone(), session_keys(
+					x.0.clone(),
 					x.1.clone(),
-					x.0.clone()
+					x.4.clone(),
+					x.5.clone(),
 				))
 			}).collect::<Vec<_>>(),
 		}),
@@ -179,13 +428,109 @@ fn testnet_genesis(
 			slash_reward_fraction: Perbill::from_percent(10),
 			.. Default::default()
 		}),
+		template: Some(Default::default()),
 	}
 }
 
 fn session_keys(
-	grandpa: GrandpaId,
 	babe: BabeId,
+	grandpa: GrandpaId,
+	im_online: ImOnlineId,
+	authority_discovery: AuthorityDiscoveryId,
 ) -> SessionKeys {
-	SessionKeys { grandpa, babe  }
+	SessionKeys { grandpa, babe, im_online, authority_discovery }
 }
 
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn endowed(authorities: &[AuthorityKeys]) -> Vec<(AccountId, Balance)> {
+		authorities.iter()
+			.flat_map(|x| vec![(x.2.clone(), ENDOWMENT), (x.3.clone(), ENDOWMENT)])
+			.collect()
+	}
+
+	#[test]
+	fn seeded_authorities_are_valid() {
+		let authorities = vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")];
+		assert_eq!(validate_genesis(&authorities, &endowed(&authorities)), Ok(()));
+	}
+
+	#[test]
+	fn empty_authorities_are_rejected() {
+		assert!(validate_genesis(&[], &[]).is_err());
+	}
+
+	#[test]
+	fn duplicate_grandpa_key_is_rejected() {
+		let mut bob = authority_keys_from_seed("Bob");
+		bob.1 = get_from_seed::<GrandpaId>("Alice");
+		let authorities = vec![authority_keys_from_seed("Alice"), bob];
+		assert_eq!(
+			validate_genesis(&authorities, &endowed(&authorities)),
+			Err("Entries #0 and #1 share the same GRANDPA key".into()),
+		);
+	}
+
+	#[test]
+	fn controller_reusing_other_stash_is_rejected() {
+		let mut bob = authority_keys_from_seed("Bob");
+		bob.3 = get_account_id_from_seed::<sr25519::Public>("Alice//stash");
+		let authorities = vec![authority_keys_from_seed("Alice"), bob];
+		assert!(validate_genesis(&authorities, &endowed(&authorities)).is_err());
+	}
+
+	#[test]
+	fn unendowed_stash_is_rejected() {
+		let authorities = vec![authority_keys_from_seed("Alice")];
+		let endowed = vec![(authorities[0].3.clone(), ENDOWMENT)];
+		assert!(validate_genesis(&authorities, &endowed).is_err());
+	}
+
+	#[test]
+	fn endowment_below_existential_deposit_is_rejected() {
+		let authorities = vec![authority_keys_from_seed("Alice")];
+		let mut endowed = endowed(&authorities);
+		endowed.push((get_account_id_from_seed::<sr25519::Public>("Dave"), 1));
+		assert!(validate_genesis(&authorities, &endowed).is_err());
+	}
+
+	#[test]
+	fn council_member_without_stake_is_rejected() {
+		let authorities = vec![authority_keys_from_seed("Alice")];
+		let dave = get_account_id_from_seed::<sr25519::Public>("Dave");
+		let mut endowed = endowed(&authorities);
+		endowed.insert(0, (dave.clone(), STASH - 1));
+		assert_eq!(
+			validate_genesis(&authorities, &endowed),
+			Err(format!("Council member {} is not endowed with enough to stake {}", dave, STASH)),
+		);
+
+		// Past the first half of the endowed accounts, it is not a council member.
+		let mut endowed = self::endowed(&authorities);
+		endowed.push((dave, STASH - 1));
+		assert_eq!(validate_genesis(&authorities, &endowed), Ok(()));
+	}
+
+	#[test]
+	fn bond_below_existential_deposit_is_rejected() {
+		assert_eq!(validate_bond(STASH), Ok(()));
+		assert_eq!(validate_bond(ExistentialDeposit::get()), Ok(()));
+		assert!(validate_bond(ExistentialDeposit::get() - 1).is_err());
+	}
+
+	#[test]
+	fn endowed_accounts_are_indexed_in_order() {
+		let authorities = vec![authority_keys_from_seed("Alice")];
+		let endowed = endowed(&authorities);
+		let genesis = testnet_genesis(
+			&[], authorities, endowed[0].0.clone(), endowed.clone(), vec![], false,
+		);
+		assert_eq!(
+			genesis.pallet_indices.unwrap().indices,
+			vec![(0, endowed[0].0.clone()), (1, endowed[1].0.clone())],
+		);
+	}
+}
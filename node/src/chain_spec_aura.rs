@@ -0,0 +1,249 @@
+//! Chain specs of the Aura build, selected with `--no-default-features --features aura`.
+
+use sp_core::{Pair, Public, sr25519};
+use node_template_runtime::{
+	AccountId, AuraConfig, BalancesConfig, ExistentialDeposit, GenesisConfig, GrandpaConfig,
+	SudoConfig, SystemConfig, WASM_BINARY, Signature, Balance, DOLLARS,
+	TOKEN_SYMBOL, TOKEN_DECIMALS, SS58_PREFIX,
+};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_finality_grandpa::AuthorityId as GrandpaId;
+use sp_runtime::traits::{Verify, IdentifyAccount};
+use sc_service::{ChainType, Properties};
+use std::collections::BTreeMap;
+
+/// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+
+/// Generate a crypto pair from seed.
+pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
+	TPublic::Pair::from_string(&format!("//{}", seed), None)
+		.expect("static values are valid; qed")
+		.public()
+}
+
+type AccountPublic = <Signature as Verify>::Signer;
+
+/// Generate an account ID from seed.
+pub fn get_account_id_from_seed<TPublic: Public>(seed: &str) -> AccountId where
+	AccountPublic: From<<TPublic::Pair as Pair>::Public>
+{
+	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
+}
+
+/// Free balance given to every endowed account of the development chains.
+pub const ENDOWMENT: Balance = 10_000 * DOLLARS;
+
+/// Keys of a genesis authority: Aura and GRANDPA, in that order.
+pub type AuthorityKeys = (AuraId, GrandpaId);
+
+/// Generate an Aura authority key.
+pub fn authority_keys_from_seed(s: &str) -> AuthorityKeys {
+	(
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+	)
+}
+
+pub fn development_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
+
+	// Initial Aura authorities
+	let initial_authorities = vec![
+		authority_keys_from_seed("Alice"),
+	];
+	// Pre-funded accounts
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+	].into_iter().map(|k| (k, ENDOWMENT)).collect::<Vec<_>>();
+	validate_genesis(&initial_authorities, &endowed_accounts)?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Development",
+		// ID
+		"dev",
+		ChainType::Development,
+		move || testnet_genesis(
+			wasm_binary,
+			initial_authorities.clone(),
+			// Sudo account
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			endowed_accounts.clone(),
+			true,
+		),
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		// Properties
+		Some(chain_properties()),
+		// Extensions
+		None,
+	))
+}
+
+pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or("Development wasm binary not available".to_string())?;
+
+	// Initial Aura authorities
+	let initial_authorities = vec![
+		authority_keys_from_seed("Alice"),
+		authority_keys_from_seed("Bob"),
+	];
+	// Pre-funded accounts
+	let endowed_accounts = vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+		get_account_id_from_seed::<sr25519::Public>("Dave"),
+		get_account_id_from_seed::<sr25519::Public>("Eve"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+	].into_iter().map(|k| (k, ENDOWMENT)).collect::<Vec<_>>();
+	validate_genesis(&initial_authorities, &endowed_accounts)?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Local Testnet",
+		// ID
+		"local_testnet",
+		ChainType::Local,
+		move || testnet_genesis(
+			wasm_binary,
+			initial_authorities.clone(),
+			// Sudo account
+			get_account_id_from_seed::<sr25519::Public>("Alice"),
+			endowed_accounts.clone(),
+			true,
+		),
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		// Properties
+		Some(chain_properties()),
+		// Extensions
+		None,
+	))
+}
+
+/// Token metadata and address format shown by wallets and explorers.
+pub fn chain_properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("tokenSymbol".into(), TOKEN_SYMBOL.into());
+	properties.insert("tokenDecimals".into(), TOKEN_DECIMALS.into());
+	properties.insert("ss58Format".into(), SS58_PREFIX.into());
+	properties
+}
+
+/// Check that a set of genesis authorities can author and finalize blocks.
+///
+/// Authorities take turns in the order given here, so a key listed twice would give its owner
+/// two slots per round and make GRANDPA count its votes twice.
+pub fn validate_genesis(
+	initial_authorities: &[AuthorityKeys],
+	endowed_accounts: &[(AccountId, Balance)],
+) -> Result<(), String> {
+	if initial_authorities.is_empty() {
+		return Err("At least one genesis authority is required".into());
+	}
+
+	ensure_unique("endowed account", endowed_accounts.iter().map(|x| &x.0))?;
+	if let Some((who, _)) = endowed_accounts.iter()
+		.find(|(_, balance)| *balance < ExistentialDeposit::get())
+	{
+		return Err(format!(
+			"Endowment of {} is below the existential deposit of {}", who, ExistentialDeposit::get(),
+		));
+	}
+
+	ensure_unique("Aura key", initial_authorities.iter().map(|x| &x.0))?;
+	ensure_unique("GRANDPA key", initial_authorities.iter().map(|x| &x.1))?;
+
+	Ok(())
+}
+
+fn ensure_unique<'a, T: Ord + 'a>(
+	what: &str,
+	keys: impl Iterator<Item = &'a T>,
+) -> Result<(), String> {
+	let mut seen = BTreeMap::new();
+	for (i, key) in keys.enumerate() {
+		if let Some(j) = seen.insert(key, i) {
+			return Err(format!("Entries #{} and #{} share the same {}", j, i, what));
+		}
+	}
+	Ok(())
+}
+
+/// Configure initial storage state for FRAME modules.
+///
+/// The authorities and endowments must have passed [`validate_genesis`].
+pub fn testnet_genesis(
+	wasm_binary: &[u8],
+	initial_authorities: Vec<AuthorityKeys>,
+	root_key: AccountId,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	_enable_println: bool,
+) -> GenesisConfig {
+	GenesisConfig {
+		frame_system: Some(SystemConfig {
+			// Add Wasm runtime to storage.
+			code: wasm_binary.to_vec(),
+			changes_trie_config: Default::default(),
+		}),
+		pallet_balances: Some(BalancesConfig {
+			balances: endowed_accounts,
+		}),
+		// Without a session pallet, the authorities are set directly and only change through sudo.
+		pallet_aura: Some(AuraConfig {
+			authorities: initial_authorities.iter().map(|x| x.0.clone()).collect(),
+		}),
+		pallet_grandpa: Some(GrandpaConfig {
+			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+		}),
+		pallet_sudo: Some(SudoConfig {
+			// Assign network admin rights.
+			key: root_key,
+		}),
+		template: Some(Default::default()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn endowed(seeds: &[&str]) -> Vec<(AccountId, Balance)> {
+		seeds.iter()
+			.map(|seed| (get_account_id_from_seed::<sr25519::Public>(seed), ENDOWMENT))
+			.collect()
+	}
+
+	#[test]
+	fn seeded_authorities_are_valid() {
+		let authorities = vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")];
+		assert_eq!(validate_genesis(&authorities, &endowed(&["Alice", "Bob"])), Ok(()));
+	}
+
+	#[test]
+	fn empty_authorities_are_rejected() {
+		assert!(validate_genesis(&[], &endowed(&["Alice"])).is_err());
+	}
+
+	#[test]
+	fn duplicate_aura_key_is_rejected() {
+		let mut bob = authority_keys_from_seed("Bob");
+		bob.0 = get_from_seed::<AuraId>("Alice");
+		let authorities = vec![authority_keys_from_seed("Alice"), bob];
+		assert_eq!(
+			validate_genesis(&authorities, &endowed(&["Alice"])),
+			Err("Entries #0 and #1 share the same Aura key".into()),
+		);
+	}
+}
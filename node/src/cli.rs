@@ -1,4 +1,4 @@
-use sc_cli::{RunCmd, Subcommand};
+use sc_cli::RunCmd;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -8,4 +8,54 @@ pub struct Cli {
 
 	#[structopt(flatten)]
 	pub run: RunCmd,
+
+	/// Seal blocks on demand instead of running BABE (or Aura) and GRANDPA: `instant` on every new
+	/// transaction, `manual` through the `engine_createBlock` RPC, or every `interval=<ms>`.
+	#[structopt(long = "sealing")]
+	pub sealing: Option<crate::sealing::Sealing>,
+
+	#[structopt(flatten)]
+	pub grandpa: GrandpaParams,
+}
+
+/// Tuning of the GRANDPA finality gadget.
+#[derive(Debug, Clone, StructOpt)]
+pub struct GrandpaParams {
+	/// Interval between gossip rounds of GRANDPA votes, in milliseconds.
+	#[structopt(long = "grandpa-gossip-duration", value_name = "MS", default_value = "333")]
+	pub gossip_duration: u64,
+
+	/// Number of blocks between two justifications stored and sent to light clients, in addition to
+	/// those of authority set changes.
+	#[structopt(long = "grandpa-justification-period", value_name = "BLOCKS", default_value = "512")]
+	pub justification_period: u32,
+
+	/// Follow finality with the GRANDPA observer instead of the full voter when not an authority.
+	#[structopt(long = "grandpa-observer")]
+	pub observer_enabled: bool,
+
+	/// Keep finality at least this many blocks behind the best block.
+	#[structopt(long = "grandpa-finality-delay", value_name = "BLOCKS")]
+	pub finality_delay: Option<u32>,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Subcommand {
+	/// The standard Substrate subcommands.
+	#[structopt(flatten)]
+	Base(sc_cli::Subcommand),
+
+	/// The custom benchmark subcommand benchmarking runtime pallets.
+	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
+	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// Generate plain and raw chain specs for a custom validator set.
+	#[cfg(feature = "babe")]
+	#[structopt(name = "generate-spec")]
+	GenerateSpec(crate::generate_spec::GenerateSpecCmd),
+
+	/// Export state snapshots and dry-run runtime upgrades against them.
+	#[cfg(feature = "babe")]
+	#[structopt(name = "snapshot")]
+	Snapshot(crate::snapshot::SnapshotCmd),
 }
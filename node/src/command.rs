@@ -16,11 +16,15 @@
 // limitations under the License.
 
 use crate::chain_spec;
-use crate::cli::Cli;
+use crate::cli::{Cli, Subcommand};
 use crate::service;
 use sc_cli::{SubstrateCli, RuntimeVersion, Role, ChainSpec};
 use sc_service::PartialComponents;
 use crate::service::new_partial;
+#[cfg(feature = "babe")]
+use crate::snapshot::SnapshotCmd;
+use node_template_runtime::{opaque::Block, SS58_PREFIX};
+use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -51,6 +55,10 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			#[cfg(feature = "babe")]
+			"staging" => Box::new(chain_spec::staging_testnet_config()?),
+			#[cfg(feature = "babe")]
+			"mainnet" => Box::new(chain_spec::mainnet_config()?),
 			path => Box::new(chain_spec::ChainSpec::from_json_file(
 				std::path::PathBuf::from(path),
 			)?),
@@ -66,8 +74,11 @@ impl SubstrateCli for Cli {
 pub fn run() -> sc_cli::Result<()> {
 	let cli = Cli::from_args();
 
-	match cli.subcommand {
-		Some(ref subcommand) => {
+	// Render and parse addresses, including those of the key subcommands, with our prefix.
+	set_default_ss58_version(Ss58AddressFormat::Custom(SS58_PREFIX));
+
+	match &cli.subcommand {
+		Some(Subcommand::Base(subcommand)) => {
 			let runner = cli.create_runner(subcommand)?;
 			runner.run_subcommand(subcommand, |config| {
 				let PartialComponents { client, backend, task_manager, import_queue, .. }
@@ -75,11 +86,34 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((client, backend, import_queue, task_manager))
 			})
 		}
+		Some(Subcommand::Benchmark(cmd)) => {
+			if cfg!(feature = "runtime-benchmarks") {
+				let runner = cli.create_runner(cmd)?;
+				runner.sync_run(|config| cmd.run::<Block, service::Executor>(config))
+			} else {
+				Err("Benchmarking wasn't enabled when building the node. \
+				You can enable it with `--features runtime-benchmarks`.".into())
+			}
+		}
+		#[cfg(feature = "babe")]
+		Some(Subcommand::GenerateSpec(cmd)) => Ok(cmd.run()?),
+		#[cfg(feature = "babe")]
+		Some(Subcommand::Snapshot(SnapshotCmd::Export(cmd))) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| {
+				let PartialComponents { client, .. } = new_partial(&config)?;
+				cmd.run(client)
+			})
+		}
+		#[cfg(feature = "babe")]
+		Some(Subcommand::Snapshot(SnapshotCmd::TryUpgrade(cmd))) => cmd.run(),
 		None => {
+			let sealing = cli.sealing;
+			let grandpa = cli.grandpa.clone();
 			let runner = cli.create_runner(&cli.run)?;
 			runner.run_node_until_exit(|config| match config.role {
 				Role::Light => service::new_light(config),
-				_ => service::new_full(config),
+				_ => service::new_full(config, sealing, grandpa),
 			})
 		}
 	}
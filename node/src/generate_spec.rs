@@ -0,0 +1,204 @@
+//! The `generate-spec` subcommand, which builds a chain spec for a custom validator set.
+
+use std::{collections::BTreeMap, fs, path::PathBuf};
+use structopt::StructOpt;
+use sc_service::ChainType;
+use sp_core::{crypto::Ss58Codec, ed25519, sr25519};
+use node_template_runtime::{AccountId, Balance, WASM_BINARY};
+use crate::chain_spec::{
+	AuthorityKeys, ChainSpec, ENDOWMENT, authority_keys_from_seed, chain_properties,
+	get_account_id_from_seed, testnet_genesis, validate_genesis,
+};
+
+/// Generate plain and raw chain specs for a custom set of validators.
+///
+/// Accounts and validators are given either as a development seed prefixed with `//`
+/// (e.g. `//Alice`) or as SS58 encoded public keys.
+#[derive(Debug, StructOpt)]
+pub struct GenerateSpecCmd {
+	/// Human readable name of the chain.
+	#[structopt(long = "name", default_value = "Custom Testnet")]
+	pub name: String,
+
+	/// Identifier of the chain, also used as the output file name.
+	#[structopt(long = "id", default_value = "custom_testnet")]
+	pub id: String,
+
+	/// Type of the chain: `live`, `local` or `development`.
+	#[structopt(long = "chain-type", default_value = "live", parse(try_from_str = parse_chain_type))]
+	pub chain_type: ChainType,
+
+	/// A genesis validator, either `//Seed` or
+	/// `stash,controller,babe,grandpa,im_online,authority_discovery` public keys.
+	#[structopt(long = "validator", required = true)]
+	pub validators: Vec<String>,
+
+	/// An endowed account with its initial balance, as `account=amount`.
+	#[structopt(long = "endow")]
+	pub endowments: Vec<String>,
+
+	/// Balance given to validator stash and controller accounts that are not explicitly endowed.
+	#[structopt(long = "validator-endowment", default_value = "1000000000000000000")]
+	pub validator_endowment: Balance,
+
+	/// The sudo key, which is also the first governance member.
+	#[structopt(long = "sudo")]
+	pub sudo: String,
+
+	/// Directory the `<id>.json` and `<id>-raw.json` specs are written to.
+	#[structopt(long = "output-dir", default_value = ".", parse(from_os_str))]
+	pub output_dir: PathBuf,
+}
+
+impl GenerateSpecCmd {
+	/// Validate the arguments and write both chain specs.
+	pub fn run(&self) -> Result<(), String> {
+		let wasm_binary = WASM_BINARY.ok_or("Wasm binary not available".to_string())?;
+
+		let initial_authorities = self.validators.iter()
+			.map(|v| parse_validator(v))
+			.collect::<Result<Vec<_>, _>>()?;
+		let root_key = parse_account(&self.sudo)?;
+
+		// Endowments are keyed by account so the output doesn't depend on argument order.
+		// The sudo key comes first since the leading accounts form the initial council.
+		let mut endowments = BTreeMap::new();
+		for endowment in &self.endowments {
+			let (who, amount) = parse_endowment(endowment)?;
+			if endowments.insert(who.clone(), amount).is_some() {
+				return Err(format!("Account {} is endowed more than once", who));
+			}
+		}
+		for (_, _, stash, controller, _, _) in &initial_authorities {
+			endowments.entry(stash.clone()).or_insert(self.validator_endowment);
+			endowments.entry(controller.clone()).or_insert(self.validator_endowment);
+		}
+		let root_endowment = endowments.remove(&root_key).unwrap_or(ENDOWMENT);
+		let endowed_accounts = Some((root_key.clone(), root_endowment)).into_iter()
+			.chain(endowments.into_iter())
+			.collect::<Vec<_>>();
+
+		validate_genesis(&initial_authorities, &endowed_accounts)?;
+
+		let spec = ChainSpec::from_genesis(
+			&self.name,
+			&self.id,
+			self.chain_type.clone(),
+			move || testnet_genesis(
+				wasm_binary,
+				initial_authorities.clone(),
+				root_key.clone(),
+				endowed_accounts.clone(),
+				// Live chains register their pairs through the council.
+				vec![],
+				false,
+			),
+			vec![],
+			None,
+			None,
+			Some(chain_properties()),
+			None,
+		);
+
+		fs::create_dir_all(&self.output_dir).map_err(|e| e.to_string())?;
+		for (raw, file) in &[(false, format!("{}.json", self.id)), (true, format!("{}-raw.json", self.id))] {
+			let path = self.output_dir.join(file);
+			fs::write(&path, spec.as_json(*raw)?)
+				.map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+		}
+
+		Ok(())
+	}
+}
+
+fn parse_chain_type(s: &str) -> Result<ChainType, String> {
+	match s {
+		"live" => Ok(ChainType::Live),
+		"local" => Ok(ChainType::Local),
+		"development" => Ok(ChainType::Development),
+		_ => Err(format!("Unknown chain type `{}`", s)),
+	}
+}
+
+fn parse_key<P: Ss58Codec>(what: &str, s: &str) -> Result<P, String> {
+	P::from_ss58check(s).map_err(|e| format!("Invalid {} `{}`: {:?}", what, s, e))
+}
+
+pub(crate) fn parse_account(s: &str) -> Result<AccountId, String> {
+	match s.strip_prefix("//") {
+		Some(seed) => Ok(get_account_id_from_seed::<sr25519::Public>(seed)),
+		None => parse_key("account", s),
+	}
+}
+
+pub(crate) fn parse_validator(s: &str) -> Result<AuthorityKeys, String> {
+	if let Some(seed) = s.strip_prefix("//") {
+		return Ok(authority_keys_from_seed(seed));
+	}
+
+	let keys = s.split(',').collect::<Vec<_>>();
+	if keys.len() != 6 {
+		return Err(format!(
+			"Validator `{}` must be `//Seed` or \
+			`stash,controller,babe,grandpa,im_online,authority_discovery`", s,
+		));
+	}
+
+	Ok((
+		parse_key::<sr25519::Public>("BABE key", keys[2])?.into(),
+		parse_key::<ed25519::Public>("GRANDPA key", keys[3])?.into(),
+		parse_key("stash account", keys[0])?,
+		parse_key("controller account", keys[1])?,
+		parse_key::<sr25519::Public>("ImOnline key", keys[4])?.into(),
+		parse_key::<sr25519::Public>("authority discovery key", keys[5])?.into(),
+	))
+}
+
+fn parse_endowment(s: &str) -> Result<(AccountId, Balance), String> {
+	let mut parts = s.splitn(2, '=');
+	let who = parse_account(parts.next().unwrap_or_default())?;
+	let amount = parts.next()
+		.ok_or_else(|| format!("Endowment `{}` must be `account=amount`", s))?
+		.parse::<Balance>()
+		.map_err(|e| format!("Invalid amount in endowment `{}`: {}", s, e))?;
+	Ok((who, amount))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::chain_spec::get_from_seed;
+
+	#[test]
+	fn seeded_validator_matches_chain_spec() {
+		assert_eq!(parse_validator("//Alice"), Ok(authority_keys_from_seed("Alice")));
+	}
+
+	#[test]
+	fn validator_public_keys_are_parsed_by_type() {
+		let alice = authority_keys_from_seed("Alice");
+		let keys = vec![
+			alice.2.to_ss58check(),
+			alice.3.to_ss58check(),
+			get_from_seed::<sr25519::Public>("Alice").to_ss58check(),
+			get_from_seed::<ed25519::Public>("Alice").to_ss58check(),
+			get_from_seed::<sr25519::Public>("Alice").to_ss58check(),
+			get_from_seed::<sr25519::Public>("Alice").to_ss58check(),
+		].join(",");
+		assert_eq!(parse_validator(&keys), Ok(alice));
+	}
+
+	#[test]
+	fn malformed_validator_is_rejected() {
+		assert!(parse_validator("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY").is_err());
+	}
+
+	#[test]
+	fn endowment_requires_amount() {
+		assert!(parse_endowment("//Bob").is_err());
+		assert_eq!(
+			parse_endowment("//Bob=1000"),
+			Ok((get_account_id_from_seed::<sr25519::Public>("Bob"), 1000)),
+		);
+	}
+}
@@ -1,3 +1,16 @@
+#[cfg(feature = "aura")]
+extern crate node_template_aura_runtime as node_template_runtime;
+
+#[cfg_attr(feature = "aura", path = "chain_spec_aura.rs")]
 pub mod chain_spec;
+pub mod cli;
+#[cfg(feature = "babe")]
+pub mod generate_spec;
+pub mod metrics;
+#[cfg_attr(feature = "aura", path = "service_aura.rs")]
 pub mod service;
 pub mod rpc;
+pub mod sealing;
+#[cfg(feature = "babe")]
+pub mod snapshot;
+pub mod voting_rule;
@@ -1,12 +1,28 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+#[cfg(all(feature = "babe", feature = "aura"))]
+compile_error!("The `babe` and `aura` features are exclusive, build with `--no-default-features --features aura`");
+
+// The Aura runtime exposes the same items as the BABE one, so the shared modules work with both.
+#[cfg(feature = "aura")]
+extern crate node_template_aura_runtime as node_template_runtime;
+
+#[cfg_attr(feature = "aura", path = "chain_spec_aura.rs")]
 mod chain_spec;
 #[macro_use]
+#[cfg_attr(feature = "aura", path = "service_aura.rs")]
 mod service;
 mod cli;
 mod command;
+#[cfg(feature = "babe")]
+mod generate_spec;
+mod metrics;
 mod rpc;
+mod sealing;
+#[cfg(feature = "babe")]
+mod snapshot;
+mod voting_rule;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,289 @@
+//! Prometheus metrics of runtime activity, fed from the events of imported blocks.
+//!
+//! Events are decoded with the `Event` type of the node's native runtime, and labelled with the
+//! names found in the metadata of the block's runtime. Records of blocks built by another runtime
+//! version, or that fail to decode, are skipped and counted in `runtime_event_records_skipped_total`.
+//!
+//! Order-book metrics, such as orders placed, cancelled and filled per pair, book depth and the
+//! weight used by matching, are not reported: the runtime has no matching engine whose events they
+//! could be fed from. `runtime_block_weight` is the weight of all the extrinsics of a block.
+
+use std::sync::Arc;
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{DecodeDifferent, RuntimeMetadata, RuntimeMetadataPrefixed};
+use futures::prelude::*;
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use sc_service::{error::Error as ServiceError, TaskManager};
+use sp_api::ProvideRuntimeApi;
+use sp_core::{storage::StorageKey, twox_128};
+use sp_runtime::{DispatchError, generic::BlockId};
+use substrate_prometheus_endpoint::{
+	exponential_buckets, register, CounterVec, Histogram, HistogramOpts, Opts, PrometheusError,
+	Registry, U64,
+};
+use node_template_runtime::{Event, Hash, VERSION, opaque::Block};
+use crate::service::FullClient;
+
+type EventRecord = frame_system::EventRecord<Event, Hash>;
+
+/// Spawn the task updating the runtime metrics on every imported block.
+pub fn start(
+	client: Arc<FullClient>,
+	registry: &Registry,
+	task_manager: &TaskManager,
+) -> Result<(), ServiceError> {
+	let metrics = Metrics::register(registry)
+		.map_err(|e| ServiceError::Other(format!("Failed to register runtime metrics: {}", e)))?;
+
+	let mut names = None;
+	let task = client.import_notification_stream().for_each(move |notification| {
+		if let Err(e) = metrics.on_block(&client, &mut names, notification.hash) {
+			log::warn!("Failed to update runtime metrics of block {}: {}", notification.hash, e);
+		}
+		future::ready(())
+	});
+
+	task_manager.spawn_handle().spawn("runtime-metrics", task);
+	Ok(())
+}
+
+/// Activity of the runtime, as seen from the blocks imported by this node.
+struct Metrics {
+	events: CounterVec<U64>,
+	skipped_records: CounterVec<U64>,
+	extrinsics: CounterVec<U64>,
+	extrinsic_failures: CounterVec<U64>,
+	block_weight: Histogram,
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Metrics {
+			events: register(CounterVec::new(
+				Opts::new("runtime_events_total", "Number of events deposited, by pallet and event"),
+				&["pallet", "event"],
+			)?, registry)?,
+			skipped_records: register(CounterVec::new(
+				Opts::new(
+					"runtime_event_records_skipped_total",
+					"Number of event records the native runtime could not decode, by reason",
+				),
+				&["reason"],
+			)?, registry)?,
+			extrinsics: register(CounterVec::new(
+				Opts::new("runtime_extrinsics_total", "Number of extrinsics applied, by result"),
+				&["result"],
+			)?, registry)?,
+			extrinsic_failures: register(CounterVec::new(
+				Opts::new("runtime_extrinsic_failures_total", "Number of failed extrinsics, by error"),
+				&["error"],
+			)?, registry)?,
+			block_weight: register(Histogram::with_opts(
+				HistogramOpts::new("runtime_block_weight", "Weight used by the extrinsics of a block")
+					.buckets(exponential_buckets(1_000_000.0, 4.0, 12)?),
+			)?, registry)?,
+		})
+	}
+
+	fn on_block(
+		&self,
+		client: &FullClient,
+		names: &mut Option<Names>,
+		hash: Hash,
+	) -> Result<(), String> {
+		let at = BlockId::Hash(hash);
+		let spec_version = client.runtime_version_at(&at).map_err(|e| e.to_string())?.spec_version;
+		if names.as_ref().map_or(true, |names| names.spec_version != spec_version) {
+			*names = Some(Names::fetch(client, &at, spec_version)?);
+		}
+		let names = names.as_ref().expect("fetched above; qed");
+
+		let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+		let data = match client.storage(&at, &key).map_err(|e| e.to_string())? {
+			Some(data) => data.0,
+			None => return Ok(()),
+		};
+		let (records, skipped) = decode_records(&data, spec_version == VERSION.spec_version)?;
+		if skipped.count > 0 {
+			self.skipped_records.with_label_values(&[skipped.reason]).inc_by(skipped.count);
+		}
+
+		let mut weight = 0;
+		for record in records {
+			let (pallet, event) = names.event(&record.event);
+			self.events.with_label_values(&[pallet, event]).inc();
+
+			match record.event {
+				Event::frame_system(frame_system::RawEvent::ExtrinsicSuccess(info)) => {
+					self.extrinsics.with_label_values(&["success"]).inc();
+					weight += info.weight;
+				}
+				Event::frame_system(frame_system::RawEvent::ExtrinsicFailed(error, info)) => {
+					self.extrinsics.with_label_values(&["failure"]).inc();
+					self.extrinsic_failures.with_label_values(&[&names.error(&error)]).inc();
+					weight += info.weight;
+				}
+				_ => {}
+			}
+		}
+		self.block_weight.observe(weight as f64);
+
+		Ok(())
+	}
+}
+
+/// Event records that were not decoded.
+#[derive(Debug, PartialEq)]
+struct Skipped {
+	count: u64,
+	reason: &'static str,
+}
+
+/// Decode the event records of a block with the native `Event` type. Records are only decoded if
+/// the block was built by the native runtime version, and up to the first one that fails to decode,
+/// since the length of the others is then unknown.
+fn decode_records(data: &[u8], native_version: bool) -> Result<(Vec<EventRecord>, Skipped), String> {
+	let input = &mut &data[..];
+	let count = u64::from(<Compact<u32>>::decode(input).map_err(|e| e.to_string())?.0);
+	if !native_version {
+		return Ok((Vec::new(), Skipped { count, reason: "runtime_version" }));
+	}
+
+	let mut records = Vec::new();
+	while (records.len() as u64) < count {
+		match EventRecord::decode(input) {
+			Ok(record) => records.push(record),
+			Err(_) => break,
+		}
+	}
+	let skipped = Skipped { count: count - records.len() as u64, reason: "undecodable" };
+	Ok((records, skipped))
+}
+
+/// Names of the pallets, events and errors of a runtime version.
+struct Names {
+	spec_version: u32,
+	/// In `construct_runtime!` order, which is how errors refer to pallets.
+	pallets: Vec<PalletNames>,
+}
+
+struct PalletNames {
+	name: String,
+	/// `None` if the pallet has no events.
+	events: Option<Vec<String>>,
+	errors: Vec<String>,
+}
+
+impl Names {
+	fn fetch(client: &FullClient, at: &BlockId<Block>, spec_version: u32) -> Result<Self, String> {
+		let metadata = client.runtime_api().metadata(at).map_err(|e| format!("{:?}", e))?;
+		let modules = match RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| e.to_string())?.1
+		{
+			RuntimeMetadata::V11(metadata) => decoded(metadata.modules)?,
+			_ => return Err("Unsupported metadata version".into()),
+		};
+
+		let pallets = modules.into_iter().map(|module| Ok(PalletNames {
+			name: decoded(module.name)?,
+			events: match module.event {
+				Some(events) => Some(decoded(events)?.into_iter()
+					.map(|event| decoded(event.name))
+					.collect::<Result<_, _>>()?),
+				None => None,
+			},
+			errors: decoded(module.errors)?.into_iter()
+				.map(|error| decoded(error.name))
+				.collect::<Result<_, _>>()?,
+		})).collect::<Result<_, String>>()?;
+
+		Ok(Names { spec_version, pallets })
+	}
+
+	/// Pallet and name of an event. Only pallets with events have an index in `Event`.
+	fn event(&self, event: &Event) -> (&str, &str) {
+		let encoded = event.encode();
+		self.pallets.iter()
+			.filter_map(|pallet| pallet.events.as_ref().map(|events| (&pallet.name, events)))
+			.nth(encoded[0] as usize)
+			.and_then(|(pallet, events)| Some((pallet.as_str(), events.get(encoded[1] as usize)?.as_str())))
+			.unwrap_or(("unknown", "unknown"))
+	}
+
+	/// Name of a dispatch error, as `Pallet::Error` for errors of pallets.
+	fn error(&self, error: &DispatchError) -> String {
+		match error {
+			DispatchError::Module { index, error, .. } => self.pallets.get(*index as usize)
+				.and_then(|pallet| Some(format!("{}::{}", pallet.name, pallet.errors.get(*error as usize)?)))
+				.unwrap_or_else(|| format!("Module({}, {})", index, error)),
+			DispatchError::Other(_) => "Other".into(),
+			DispatchError::CannotLookup => "CannotLookup".into(),
+			DispatchError::BadOrigin => "BadOrigin".into(),
+		}
+	}
+}
+
+/// Metadata decoded from its SCALE encoding only ever has decoded values.
+fn decoded<B: 'static, O: 'static>(value: DecodeDifferent<B, O>) -> Result<O, String> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("Metadata is not decoded".into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn names() -> Names {
+		let pallet = |name: &str, events: Option<&[&str]>, errors: &[&str]| PalletNames {
+			name: name.into(),
+			events: events.map(|events| events.iter().map(|e| e.to_string()).collect()),
+			errors: errors.iter().map(|e| e.to_string()).collect(),
+		};
+		Names {
+			spec_version: 1,
+			pallets: vec![
+				pallet("System", Some(&["ExtrinsicSuccess", "ExtrinsicFailed"]), &["InvalidSpecName"]),
+				pallet("RandomnessCollectiveFlip", None, &[]),
+				pallet("TemplateModule", Some(&["SomethingStored"]), &["NoneValue", "StorageOverflow"]),
+			],
+		}
+	}
+
+	#[test]
+	fn module_errors_are_named_after_their_pallet() {
+		let names = names();
+		let error = DispatchError::Module { index: 2, error: 1, message: None };
+		assert_eq!(names.error(&error), "TemplateModule::StorageOverflow");
+		let error = DispatchError::Module { index: 7, error: 0, message: None };
+		assert_eq!(names.error(&error), "Module(7, 0)");
+		assert_eq!(names.error(&DispatchError::BadOrigin), "BadOrigin");
+	}
+
+	#[test]
+	fn records_are_decoded_up_to_the_first_undecodable_one() {
+		let record = EventRecord {
+			phase: frame_system::Phase::Initialization,
+			event: Event::frame_system(frame_system::RawEvent::CodeUpdated),
+			topics: vec![],
+		};
+		let mut data = vec![record.clone(), record.clone()].encode();
+		assert_eq!(
+			decode_records(&data, true),
+			Ok((vec![record.clone(), record.clone()], Skipped { count: 0, reason: "undecodable" })),
+		);
+		assert_eq!(
+			decode_records(&data, false),
+			Ok((vec![], Skipped { count: 2, reason: "runtime_version" })),
+		);
+
+		// A third record, from a pallet the native runtime doesn't have.
+		data[0] = Compact(3u32).encode()[0];
+		data.extend_from_slice(&[2, u8::max_value()]);
+		assert_eq!(
+			decode_records(&data, true),
+			Ok((vec![record.clone(), record], Skipped { count: 1, reason: "undecodable" })),
+		);
+	}
+}
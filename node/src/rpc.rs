@@ -7,45 +7,135 @@
 
 use std::sync::Arc;
 
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use codec::{Decode, Encode};
+use futures::{FutureExt, TryFutureExt};
+use jsonrpc_core::{BoxFuture, Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use pallet_transaction_payment_rpc::TransactionPaymentApi;
+use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
+use sc_client_api::light::{future_header, Fetcher, RemoteBlockchain, RemoteCallRequest};
+use sc_finality_grandpa::{
+	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+};
+use sc_rpc::SubscriptionTaskExecutor;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
+use sp_core::Bytes;
 pub use sc_rpc_api::DenyUnsafe;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use sp_transaction_pool::TransactionPool;
 
 
+/// Extra dependencies for BABE.
+#[cfg(feature = "babe")]
+pub struct BabeDeps {
+	/// BABE protocol config.
+	pub babe_config: sc_consensus_babe::Config,
+	/// BABE pending epoch changes.
+	pub shared_epoch_changes:
+		sc_consensus_epochs::SharedEpochChanges<Block, sc_consensus_babe::Epoch>,
+	/// The keystore that manages the keys of the node.
+	pub keystore: sc_keystore::KeyStorePtr,
+	/// The chain whose best block slot claims are computed from.
+	pub select_chain: crate::service::FullSelectChain,
+}
+
+/// Runtime API of the consensus engine the node is built with, as needed by its RPCs.
+#[cfg(feature = "babe")]
+pub trait ConsensusRuntimeApi: sp_consensus_babe::BabeApi<Block> {}
+
+#[cfg(feature = "babe")]
+impl<T: sp_consensus_babe::BabeApi<Block>> ConsensusRuntimeApi for T {}
+
+/// Runtime API of the consensus engine the node is built with, as needed by its RPCs.
+#[cfg(feature = "aura")]
+pub trait ConsensusRuntimeApi {}
+
+#[cfg(feature = "aura")]
+impl<T> ConsensusRuntimeApi for T {}
+
+/// Extra dependencies for GRANDPA
+pub struct GrandpaDeps<B> {
+	/// Voting round info.
+	pub shared_voter_state: SharedVoterState,
+	/// Authority set info.
+	pub shared_authority_set: SharedAuthoritySet<Hash, BlockNumber>,
+	/// Receives notifications about justification events from Grandpa.
+	pub justification_stream: GrandpaJustificationStream<Block>,
+	/// Executor to drive the subscription manager in the Grandpa RPC handler.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Finality proof provider.
+	pub finality_provider: Arc<FinalityProofProvider<B, Block>>,
+}
+
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, B> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Sink of the manual seal engine RPCs, when sealing is enabled.
+	pub command_sink: Option<crate::sealing::CommandSink>,
+	/// BABE specific dependencies.
+	#[cfg(feature = "babe")]
+	pub babe: BabeDeps,
+	/// GRANDPA specific dependencies.
+	pub grandpa: GrandpaDeps<B>,
+}
+
+/// Light client extra dependencies.
+pub struct LightDeps<C, F, P> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+	/// Transaction pool instance.
+	pub pool: Arc<P>,
+	/// Remote access to the blockchain (async).
+	pub remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+	/// Fetcher instance.
+	pub fetcher: Arc<F>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, B>(
+	deps: FullDeps<C, P, B>,
 ) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
+	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: ConsensusRuntimeApi,
 	P: TransactionPool + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
-	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_transaction_payment_rpc::TransactionPayment;
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApi};
+	use sc_finality_grandpa_rpc::{GrandpaApi, GrandpaRpcHandler};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
 		client,
 		pool,
 		deny_unsafe,
+		command_sink,
+		#[cfg(feature = "babe")]
+		babe,
+		grandpa,
 	} = deps;
+	let GrandpaDeps {
+		shared_voter_state,
+		shared_authority_set,
+		justification_stream,
+		subscription_executor,
+		finality_provider,
+	} = grandpa;
 
 	io.extend_with(
 		SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe))
@@ -55,6 +145,47 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
+	#[cfg(feature = "babe")]
+	{
+		use sc_consensus_babe_rpc::{BabeApi, BabeRpcHandler};
+
+		let BabeDeps {
+			babe_config,
+			shared_epoch_changes,
+			keystore,
+			select_chain,
+		} = babe;
+
+		io.extend_with(
+			BabeApi::to_delegate(BabeRpcHandler::new(
+				client.clone(),
+				shared_epoch_changes,
+				keystore,
+				babe_config,
+				select_chain,
+				deny_unsafe,
+			))
+		);
+	}
+
+	io.extend_with(
+		GrandpaApi::to_delegate(GrandpaRpcHandler::new(
+			shared_authority_set,
+			shared_voter_state,
+			justification_stream,
+			subscription_executor,
+			finality_provider,
+		))
+	);
+
+	if let Some(command_sink) = command_sink {
+		io.extend_with(
+			// We provide the rpc handler with the sending end of the channel to allow the rpc
+			// send EngineCommands to the background block authorship task.
+			ManualSealApi::to_delegate(ManualSeal::new(command_sink)),
+		);
+	}
+
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -62,3 +193,116 @@ pub fn create_full<C, P>(
 
 	io
 }
+
+/// Instantiate the RPC extensions a light client can serve, answering them with calls to full
+/// nodes.
+pub fn create_light<C, P, F>(
+	deps: LightDeps<C, F, P>,
+) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
+	C: HeaderBackend<Block>,
+	C: Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+	P: TransactionPool + 'static,
+{
+	use substrate_frame_rpc_system::{LightSystem, SystemApi};
+
+	let LightDeps {
+		client,
+		pool,
+		remote_blockchain,
+		fetcher,
+	} = deps;
+	let mut io = jsonrpc_core::IoHandler::default();
+
+	io.extend_with(
+		SystemApi::<Hash, AccountId, Index>::to_delegate(
+			LightSystem::new(client.clone(), remote_blockchain.clone(), fetcher.clone(), pool)
+		)
+	);
+
+	io.extend_with(
+		LightTransactionPaymentApi::to_delegate(
+			LightTransactionPayment::new(client, remote_blockchain, fetcher)
+		)
+	);
+
+	io
+}
+
+/// `payment_queryInfo` of a light client. Unlike `TransactionPaymentApi`, the answer is a future,
+/// so the RPC thread doesn't wait for the full node.
+#[rpc]
+pub trait LightTransactionPaymentApi<BlockHash, ResponseType> {
+	/// The dispatch info of an encoded extrinsic, at the best block by default.
+	#[rpc(name = "payment_queryInfo")]
+	fn query_info(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> BoxFuture<ResponseType>;
+}
+
+/// `payment_queryInfo` of a light client, executed by a full node through a remote call.
+pub struct LightTransactionPayment<C, F> {
+	client: Arc<C>,
+	remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+	fetcher: Arc<F>,
+}
+
+impl<C, F> LightTransactionPayment<C, F> {
+	/// Create new `LightTransactionPayment` with the given reference to the client.
+	pub fn new(
+		client: Arc<C>,
+		remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+		fetcher: Arc<F>,
+	) -> Self {
+		LightTransactionPayment { client, remote_blockchain, fetcher }
+	}
+}
+
+impl<C, F> LightTransactionPaymentApi<Hash, RuntimeDispatchInfo<Balance>>
+	for LightTransactionPayment<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	fn query_info(
+		&self,
+		encoded_xt: Bytes,
+		at: Option<Hash>,
+	) -> BoxFuture<RuntimeDispatchInfo<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let encoded_len = encoded_xt.len() as u32;
+		let uxt = match <Block as BlockT>::Extrinsic::decode(&mut &*encoded_xt) {
+			Ok(uxt) => uxt,
+			Err(e) => return Box::new(jsonrpc_core::futures::future::err(
+				rpc_error(2, "Unable to query dispatch info.", e),
+			)),
+		};
+
+		let remote_blockchain = self.remote_blockchain.clone();
+		let fetcher = self.fetcher.clone();
+		let info = async move {
+			let encoded_info = async {
+				let header = future_header(&*remote_blockchain, &*fetcher, BlockId::Hash(at)).await?
+					.ok_or_else(|| BlockChainError::UnknownBlock(format!("{}", at)))?;
+				fetcher.remote_call(RemoteCallRequest {
+					block: at,
+					header,
+					method: "TransactionPaymentApi_query_info".into(),
+					call_data: (uxt, encoded_len).encode(),
+					retry_count: None,
+				}).await
+			}.await.map_err(|e| rpc_error(1, "Unable to query dispatch info.", e))?;
+
+			RuntimeDispatchInfo::decode(&mut &encoded_info[..])
+				.map_err(|e| rpc_error(2, "Unable to decode dispatch info.", e))
+		};
+
+		Box::new(info.boxed().compat())
+	}
+}
+
+fn rpc_error(code: i64, message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(code),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
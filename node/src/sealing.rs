@@ -0,0 +1,280 @@
+//! Manual, instant and interval sealing, which replace BABE (or Aura) and GRANDPA on development
+//! chains.
+//!
+//! The BABE runtime expects a BABE pre-digest in every block, so sealed blocks carry a secondary
+//! slot claim of the first authority for the slot of their timestamp. No VRF is involved, since
+//! sealed blocks are imported without BABE's verification.
+
+use std::{
+	str::FromStr, sync::{Arc, atomic::{AtomicU64, Ordering}},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use codec::Decode;
+use futures::{prelude::*, channel::mpsc, stream::{self, BoxStream}};
+use sc_client_api::StorageProvider;
+use sc_consensus_manual_seal::{EngineCommand, ManualSealParams, run_manual_seal};
+#[cfg(feature = "babe")]
+use sc_consensus_manual_seal::{Error as SealError, consensus::ConsensusDataProvider};
+use sc_service::{error::Error as ServiceError, TaskManager};
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, twox_128};
+use sp_inherents::{InherentData, InherentDataProviders, InherentIdentifier, ProvideInherentData};
+use sp_runtime::generic::BlockId;
+#[cfg(feature = "babe")]
+use sp_consensus_babe::digests::{CompatibleDigestItem, PreDigest, SecondaryPlainPreDigest};
+#[cfg(feature = "babe")]
+use sp_runtime::{Digest, DigestItem, traits::DigestFor};
+#[cfg(feature = "babe")]
+use sp_timestamp::TimestampInherentData;
+use substrate_prometheus_endpoint::Registry;
+use node_template_runtime::{Hash, SLOT_DURATION, opaque::Block};
+use crate::service::{FullClient, FullSelectChain};
+
+/// How blocks are sealed when manual sealing is enabled with `--sealing`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when asked to through `engine_createBlock`.
+	Manual,
+	/// Seal a block every given number of milliseconds, even if it's empty.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			_ => s.strip_prefix("interval=")
+				.and_then(|millis| millis.parse().ok())
+				.filter(|millis| *millis > 0)
+				.map(Sealing::Interval)
+				.ok_or_else(|| format!("Expected `instant`, `manual` or `interval=<ms>`, got `{}`", s)),
+		}
+	}
+}
+
+/// Where the `engine_createBlock` and `engine_finalizeBlock` RPCs send their commands.
+pub type CommandSink = mpsc::Sender<EngineCommand<Hash>>;
+
+/// Create the channel between the engine RPCs and the sealing task.
+pub fn command_channel() -> (CommandSink, mpsc::Receiver<EngineCommand<Hash>>) {
+	mpsc::channel(1024)
+}
+
+/// Spawn the task sealing blocks on `rpc_commands` and, depending on `sealing`, on new
+/// transactions or a timer. Every sealed block is finalized right away.
+pub fn start(
+	sealing: Sealing,
+	rpc_commands: mpsc::Receiver<EngineCommand<Hash>>,
+	client: Arc<FullClient>,
+	transaction_pool: Arc<sc_transaction_pool::FullPool<Block, FullClient>>,
+	select_chain: FullSelectChain,
+	prometheus_registry: Option<&Registry>,
+	task_manager: &TaskManager,
+) -> Result<(), ServiceError> {
+	let inherent_data_providers = InherentDataProviders::new();
+	inherent_data_providers
+		.register_provider(SlotTimestampProvider(AtomicU64::new(first_timestamp(&client)?)))
+		.map_err(|e| ServiceError::Other(format!("{:?}", e)))?;
+
+	let seal = |create_empty| EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+	let commands_stream: BoxStream<'static, _> = match sealing {
+		Sealing::Manual => rpc_commands.boxed(),
+		Sealing::Instant => stream::select(
+			rpc_commands,
+			transaction_pool.pool().validated_pool().import_notification_stream()
+				.map(move |_| seal(false)),
+		).boxed(),
+		Sealing::Interval(millis) => stream::select(
+			rpc_commands,
+			stream::unfold((), move |_| {
+				futures_timer::Delay::new(Duration::from_millis(millis)).map(|_| Some(((), ())))
+			}).map(move |_| seal(true)),
+		).boxed(),
+	};
+
+	let env = sc_basic_authorship::ProposerFactory::new(
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry,
+	);
+
+	let authorship_future = run_manual_seal(ManualSealParams {
+		block_import: client.clone(),
+		env,
+		client,
+		pool: transaction_pool.pool().clone(),
+		commands_stream,
+		select_chain,
+		#[cfg(feature = "babe")]
+		consensus_data_provider: Some(Box::new(BabePreDigestProvider)),
+		#[cfg(feature = "aura")]
+		consensus_data_provider: None,
+		inherent_data_providers,
+	});
+
+	// the sealing task is considered essential, i.e. if it
+	// fails we take down the service with it.
+	task_manager.spawn_essential_handle().spawn_blocking("manual-seal", authorship_future);
+	Ok(())
+}
+
+/// Timestamp of the first sealed block: now, or right after the best block if that is later
+/// because blocks were sealed faster than real time.
+fn first_timestamp(client: &FullClient) -> Result<u64, ServiceError> {
+	let key = StorageKey([twox_128(b"Timestamp"), twox_128(b"Now")].concat());
+	let best = client.storage(&BlockId::Hash(client.info().best_hash), &key)?
+		.and_then(|data| u64::decode(&mut &data.0[..]).ok())
+		.unwrap_or_default();
+	let now = SystemTime::now().duration_since(UNIX_EPOCH)
+		.map(|since_epoch| since_epoch.as_millis() as u64)
+		.unwrap_or_default();
+	Ok(now.max(best + SLOT_DURATION))
+}
+
+/// Provides timestamps one slot apart, however quickly blocks are sealed, so the timestamp
+/// pallet's minimum period between blocks is always respected.
+struct SlotTimestampProvider(AtomicU64);
+
+impl ProvideInherentData for SlotTimestampProvider {
+	fn inherent_identifier(&self) -> &'static InherentIdentifier {
+		&sp_timestamp::INHERENT_IDENTIFIER
+	}
+
+	fn provide_inherent_data(&self, inherent_data: &mut InherentData) -> Result<(), sp_inherents::Error> {
+		let timestamp = self.0.fetch_add(SLOT_DURATION, Ordering::SeqCst);
+		inherent_data.put_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp)
+	}
+
+	fn error_to_string(&self, error: &[u8]) -> Option<String> {
+		sp_timestamp::InherentError::try_from(&sp_timestamp::INHERENT_IDENTIFIER, error)
+			.map(|e| format!("{:?}", e))
+	}
+}
+
+/// Writes the BABE pre-digest of sealed blocks, for the slot of the timestamp they are built with.
+#[cfg(feature = "babe")]
+struct BabePreDigestProvider;
+
+#[cfg(feature = "babe")]
+impl ConsensusDataProvider<Block> for BabePreDigestProvider {
+	type Transaction = sp_api::TransactionFor<FullClient, Block>;
+
+	fn create_digest(
+		&self,
+		_parent: &<Block as sp_runtime::traits::Block>::Header,
+		inherents: &InherentData,
+	) -> Result<DigestFor<Block>, SealError> {
+		let timestamp = inherents.timestamp_inherent_data()
+			.map_err(|e| SealError::StringError(format!("No timestamp to seal with: {:?}", e)))?;
+		let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 0,
+			slot_number: timestamp / SLOT_DURATION,
+		});
+		Ok(Digest { logs: vec![<DigestItem<Hash> as CompatibleDigestItem>::babe_pre_digest(pre_digest)] })
+	}
+
+	fn append_block_import(
+		&self,
+		_parent: &<Block as sp_runtime::traits::Block>::Header,
+		_params: &mut sp_consensus::BlockImportParams<Block, Self::Transaction>,
+		_inherents: &InherentData,
+	) -> Result<(), SealError> {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::channel::oneshot;
+	use sc_service::TaskExecutor;
+	use sp_runtime::BuildStorage;
+	use substrate_test_client::{GenesisInit, TestClientBuilder};
+
+	/// Genesis of the development chain.
+	#[derive(Default)]
+	struct DevGenesis;
+
+	impl GenesisInit for DevGenesis {
+		fn genesis_storage(&self) -> sp_core::storage::Storage {
+			crate::chain_spec::development_config().unwrap().build_storage().unwrap()
+		}
+	}
+
+	#[test]
+	fn manual_sealing_builds_on_sealed_blocks() {
+		let (client, select_chain): (FullClient, _) =
+			TestClientBuilder::<Block, _, _, DevGenesis>::with_default_backend()
+				.build_with_native_executor(None);
+		let client = Arc::new(client);
+		let executor = TaskExecutor::from(|task, _| {
+			std::thread::spawn(move || futures::executor::block_on(task));
+			future::ready(())
+		});
+		let task_manager = TaskManager::new(executor, None).unwrap();
+		let transaction_pool = sc_transaction_pool::BasicPool::new_full(
+			Default::default(),
+			None,
+			task_manager.spawn_handle(),
+			client.clone(),
+		);
+		let (mut command_sink, rpc_commands) = command_channel();
+		start(
+			Sealing::Manual,
+			rpc_commands,
+			client.clone(),
+			transaction_pool,
+			select_chain,
+			None,
+			&task_manager,
+		).unwrap();
+
+		#[cfg(feature = "babe")]
+		let mut slots = Vec::new();
+		for number in 1..=2 {
+			let (sender, receiver) = oneshot::channel();
+			futures::executor::block_on(command_sink.send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize: true,
+				parent_hash: None,
+				sender: Some(sender),
+			})).unwrap();
+			let created = futures::executor::block_on(receiver).unwrap().unwrap();
+
+			assert_eq!(client.info().best_number, number);
+			assert_eq!(client.info().finalized_hash, created.hash);
+			#[cfg(feature = "babe")]
+			{
+				use sp_runtime::traits::Header as _;
+				let header = client.header(&BlockId::Hash(created.hash)).unwrap().unwrap();
+				let pre_digest = header.digest().logs().iter()
+					.find_map(|log| log.as_babe_pre_digest())
+					.expect("sealed blocks carry a BABE pre-digest");
+				assert_eq!(pre_digest.authority_index(), 0);
+				slots.push(pre_digest.slot_number());
+			}
+		}
+		#[cfg(feature = "babe")]
+		assert_eq!(slots[1], slots[0] + 1);
+	}
+
+	#[test]
+	fn sealing_is_parsed() {
+		assert_eq!("instant".parse(), Ok(Sealing::Instant));
+		assert_eq!("manual".parse(), Ok(Sealing::Manual));
+		assert_eq!("interval=250".parse(), Ok(Sealing::Interval(250)));
+		assert!("interval=0".parse::<Sealing>().is_err());
+		assert!("interval".parse::<Sealing>().is_err());
+		assert!("babe".parse::<Sealing>().is_err());
+	}
+}
@@ -4,10 +4,12 @@ use std::sync::Arc;
 use std::time::Duration;
 use sc_client_api::{ExecutorProvider, RemoteBackend};
 use node_template_runtime::{self, opaque::Block, RuntimeApi};
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_service::{error::Error as ServiceError, config::Role, Configuration, TaskManager};
+use sc_network::Event;
+use futures::prelude::*;
+use crate::{cli::GrandpaParams, sealing::Sealing, voting_rule::BehindBestBlockBy};
 use sc_executor::native_executor_instance;
 pub use sc_executor::NativeExecutor;
-// use sp_consensus_aura::sr25519::{AuthorityPair as AuraPair};
 use sc_consensus_babe;
 use sc_finality_grandpa::{FinalityProofProvider as GrandpaFinalityProofProvider, SharedVoterState};
 
@@ -16,11 +18,12 @@ native_executor_instance!(
 	pub Executor,
 	node_template_runtime::api::dispatch,
 	node_template_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
 );
 
-type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
+pub(crate) type FullClient = sc_service::TFullClient<Block, RuntimeApi, Executor>;
 type FullBackend = sc_service::TFullBackend<Block>;
-type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+pub(crate) type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 type FullGrandpaBlockImport =
 sc_finality_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
 
@@ -42,6 +45,13 @@ pub fn new_partial(config: &Configuration) -> Result<sc_service::PartialComponen
 
 	let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
+	// Uncles are needed by the authorship pallet to credit block authors.
+	sc_consensus_uncles::register_uncles_inherent_data_provider(
+		client.clone(),
+		select_chain.clone(),
+		&inherent_data_providers,
+	)?;
+
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
 		config.prometheus_registry(),
@@ -54,28 +64,12 @@ pub fn new_partial(config: &Configuration) -> Result<sc_service::PartialComponen
 	)?;
 
 	let justification_import = grandpa_block_import.clone();
-	// let aura_block_import = sc_consensus_aura::AuraBlockImport::<_, _, _, AuraPair>::new(
-	// 	grandpa_block_import.clone(), client.clone(),
-	// );
-
 	let (block_import, babe_link) = sc_consensus_babe::block_import(
 		sc_consensus_babe::Config::get_or_compute(&*client)?,
 		grandpa_block_import,
 		client.clone(),
 	)?;
 
-	// let import_queue = sc_consensus_aura::import_queue::<_, _, _, AuraPair, _, _>(
-	// 	sc_consensus_aura::slot_duration(&*client)?,
-	// 	aura_block_import,
-	// 	Some(Box::new(grandpa_block_import.clone())),
-	// 	None,
-	// 	client.clone(),
-	// 	inherent_data_providers.clone(),
-	// 	&task_manager.spawn_handle(),
-	// 	config.prometheus_registry(),
-	// 	sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone()),
-	// )?;
-
 	let import_queue = sc_consensus_babe::import_queue(
 		babe_link.clone(),
 		block_import.clone(),
@@ -97,7 +91,13 @@ pub fn new_partial(config: &Configuration) -> Result<sc_service::PartialComponen
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `sealing` set, blocks are sealed on demand instead of by BABE and GRANDPA.
+pub fn new_full(
+	config: Configuration,
+	sealing: Option<Sealing>,
+	grandpa: GrandpaParams,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client, backend, mut task_manager, import_queue, keystore, select_chain, transaction_pool,
 		inherent_data_providers,
@@ -133,15 +133,48 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let telemetry_connection_sinks = sc_service::TelemetryConnectionSinks::default();
 
+	let (command_sink, rpc_commands) = match sealing {
+		Some(_) => {
+			let (command_sink, rpc_commands) = crate::sealing::command_channel();
+			(Some(command_sink), Some(rpc_commands))
+		}
+		None => (None, None),
+	};
+
+	// Shared with the RPC, which reports the state of the voter's rounds.
+	let shared_voter_state = SharedVoterState::empty();
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
-
-		Box::new(move |deny_unsafe, _| {
+		let justification_stream = grandpa_link.justification_stream();
+		let shared_authority_set = grandpa_link.shared_authority_set().clone();
+		let shared_voter_state = shared_voter_state.clone();
+		let finality_proof_provider = finality_proof_provider.clone();
+		let babe_config = babe_link.config().clone();
+		let shared_epoch_changes = babe_link.epoch_changes().clone();
+		let keystore = keystore.clone();
+		let select_chain = select_chain.clone();
+
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
+				command_sink: command_sink.clone(),
+				babe: crate::rpc::BabeDeps {
+					babe_config: babe_config.clone(),
+					shared_epoch_changes: shared_epoch_changes.clone(),
+					keystore: keystore.clone(),
+					select_chain: select_chain.clone(),
+				},
+				grandpa: crate::rpc::GrandpaDeps {
+					shared_voter_state: shared_voter_state.clone(),
+					shared_authority_set: shared_authority_set.clone(),
+					justification_stream: justification_stream.clone(),
+					subscription_executor,
+					finality_provider: finality_proof_provider.clone(),
+				},
 			};
 
 			crate::rpc::create_full(deps)
@@ -161,6 +194,25 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		backend, network_status_sinks, system_rpc_tx, config,
 	})?;
 
+	if let Some(registry) = prometheus_registry.as_ref() {
+		crate::metrics::start(client.clone(), registry, &task_manager)?;
+	}
+
+	if let (Some(sealing), Some(rpc_commands)) = (sealing, rpc_commands) {
+		crate::sealing::start(
+			sealing,
+			rpc_commands,
+			client,
+			transaction_pool,
+			select_chain,
+			prometheus_registry.as_ref(),
+			&task_manager,
+		)?;
+
+		network_starter.start_network();
+		return Ok(task_manager);
+	}
+
 	if role.is_authority() {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			client.clone(),
@@ -171,18 +223,6 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		let can_author_with =
 			sp_consensus::CanAuthorWithNativeVersion::new(client.executor().clone());
 
-		// let aura = sc_consensus_aura::start_aura::<_, _, _, _, _, AuraPair, _, _, _>(
-		// 	sc_consensus_aura::slot_duration(&*client)?,
-		// 	client.clone(),
-		// 	select_chain,
-		// 	block_import,
-		// 	proposer,
-		// 	network.clone(),
-		// 	inherent_data_providers.clone(),
-		// 	force_authoring,
-		// 	keystore.clone(),
-		// 	can_author_with,
-		// )?;
 		let babe_config = sc_consensus_babe::BabeParams {
 			keystore: keystore.clone(),
 			client: client.clone(),
@@ -198,11 +238,42 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 
 		let babe = sc_consensus_babe::start_babe(babe_config)?;
 
-		// the AURA authoring task is considered essential, i.e. if it
+		// the BABE authoring task is considered essential, i.e. if it
 		// fails we take down the service with it.
 		task_manager.spawn_essential_handle().spawn_blocking("babe-proposer", babe);
 	}
 
+	// Spawn authority discovery module.
+	if matches!(role, Role::Authority{..} | Role::Sentry{..}) {
+		let (sentries, authority_discovery_role) = match role {
+			Role::Authority { ref sentry_nodes } => (
+				sentry_nodes.clone(),
+				sc_authority_discovery::Role::Authority(keystore.clone()),
+			),
+			Role::Sentry { .. } => (
+				vec![],
+				sc_authority_discovery::Role::Sentry,
+			),
+			_ => unreachable!("Due to outer matches! constraint; qed."),
+		};
+
+		let dht_event_stream = network.event_stream("authority-discovery")
+			.filter_map(|e| async move { match e {
+				Event::Dht(e) => Some(e),
+				_ => None,
+			}}).boxed();
+		let (authority_discovery_worker, _service) = sc_authority_discovery::new_worker_and_service(
+			client.clone(),
+			network.clone(),
+			sentries,
+			dht_event_stream,
+			authority_discovery_role,
+			prometheus_registry.clone(),
+		);
+
+		task_manager.spawn_handle().spawn("authority-discovery-worker", authority_discovery_worker);
+	}
+
 	// if the node isn't actively participating in consensus then it doesn't
 	// need a keystore, regardless of which protocol we use below.
 	let keystore = if role.is_authority() {
@@ -212,16 +283,28 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	};
 
 	let grandpa_config = sc_finality_grandpa::Config {
-		// FIXME #1578 make this available through chainspec
-		gossip_duration: Duration::from_millis(333),
-		justification_period: 512,
+		gossip_duration: Duration::from_millis(grandpa.gossip_duration),
+		justification_period: grandpa.justification_period,
 		name: Some(name),
-		observer_enabled: false,
+		observer_enabled: grandpa.observer_enabled,
 		keystore,
 		is_authority: role.is_network_authority(),
 	};
 
-	if enable_grandpa {
+	let voting_rule = match grandpa.finality_delay {
+		Some(delay) => sc_finality_grandpa::VotingRulesBuilder::default()
+			.add(BehindBestBlockBy(delay))
+			.build(),
+		None => sc_finality_grandpa::VotingRulesBuilder::default().build(),
+	};
+
+	if enable_grandpa && grandpa.observer_enabled && !role.is_authority() {
+		// the observer follows finality from the votes of others without ever voting
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"grandpa-observer",
+			sc_finality_grandpa::run_grandpa_observer(grandpa_config, grandpa_link, network)?
+		);
+	} else if enable_grandpa {
 		// start the full GRANDPA voter
 		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
 		// this point the full voter should provide better guarantees of block
@@ -234,9 +317,9 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			network,
 			inherent_data_providers,
 			telemetry_on_connect: Some(telemetry_connection_sinks.on_connect_stream()),
-			voting_rule: sc_finality_grandpa::VotingRulesBuilder::default().build(),
+			voting_rule,
 			prometheus_registry,
-			shared_voter_state: SharedVoterState::empty(),
+			shared_voter_state,
 		};
 
 		// the GRANDPA voter task is considered infallible, i.e.
@@ -280,17 +363,6 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let finality_proof_request_builder =
 		finality_proof_import.create_finality_proof_request_builder();
 
-	// let import_queue = sc_consensus_aura::import_queue::<_, _, _, AuraPair, _, _>(
-	// 	sc_consensus_aura::slot_duration(&*client)?,
-	// 	grandpa_block_import,
-	// 	None,
-	// 	Some(Box::new(finality_proof_import)),
-	// 	client.clone(),
-	// 	InherentDataProviders::new(),
-	// 	&task_manager.spawn_handle(),
-	// 	config.prometheus_registry(),
-	// 	sp_consensus::NeverCanAuthor,
-	// )?;
 	let (babe_block_import, babe_link) = sc_consensus_babe::block_import(
 		sc_consensus_babe::Config::get_or_compute(&*client)?,
 		grandpa_block_import,
@@ -334,12 +406,20 @@ pub fn new_light(config: Configuration) -> Result<TaskManager, ServiceError> {
 		);
 	}
 
+	let light_deps = crate::rpc::LightDeps {
+		remote_blockchain: backend.remote_blockchain(),
+		fetcher: on_demand.clone(),
+		client: client.clone(),
+		pool: transaction_pool.clone(),
+	};
+	let rpc_extensions = crate::rpc::create_light(light_deps);
+
 	sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		remote_blockchain: Some(backend.remote_blockchain()),
 		transaction_pool,
 		task_manager: &mut task_manager,
 		on_demand: Some(on_demand),
-		rpc_extensions_builder: Box::new(|_, _| ()),
+		rpc_extensions_builder: Box::new(sc_service::NoopRpcExtensionBuilder(rpc_extensions)),
 		telemetry_connection_sinks: sc_service::TelemetryConnectionSinks::default(),
 		config,
 		client,
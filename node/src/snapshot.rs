@@ -0,0 +1,313 @@
+//! The `snapshot` subcommands: export the state of a block to a file and dry-run a runtime upgrade
+//! against it, fully offline.
+
+use std::{fs, path::PathBuf, sync::Arc, collections::BTreeMap};
+use codec::{Encode, Decode};
+use structopt::StructOpt;
+use sc_cli::{
+	CliConfiguration, DatabaseParams, PruningParams, SharedParams, WasmExecutionMethod,
+};
+use sc_executor::NativeExecutor;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	NeverNativeValue, NativeOrEncoded, blake2_256, twox_128,
+	storage::{ChildInfo, Storage, StorageChild, well_known_keys},
+	traits::{CodeExecutor, RuntimeCode, WrappedRuntimeCode},
+};
+use sp_consensus_babe::digests::{CompatibleDigestItem, PreDigest, SecondaryPlainPreDigest};
+use sp_inherents::InherentData;
+use sp_runtime::{
+	ApplyExtrinsicResult, Digest, DigestItem, RuntimeString,
+	generic::BlockId,
+	traits::Header as HeaderT,
+};
+use sp_state_machine::BasicExternalities;
+use node_template_runtime::{
+	BlockNumber, Hash, Header, SLOT_DURATION, Weight, opaque::{Block, UncheckedExtrinsic},
+};
+use crate::service::{Executor, FullClient};
+
+/// Take state snapshots and test runtime upgrades against them.
+#[derive(Debug, StructOpt)]
+pub enum SnapshotCmd {
+	/// Export the full state at a block of the local database to a file.
+	Export(ExportSnapshotCmd),
+
+	/// Run a runtime upgrade and a few blocks against a snapshot, without touching any database.
+	TryUpgrade(TryUpgradeCmd),
+}
+
+/// State of a block, as written by `snapshot export`.
+#[derive(Encode, Decode)]
+struct Snapshot {
+	number: BlockNumber,
+	hash: Hash,
+	top: Vec<(Vec<u8>, Vec<u8>)>,
+	children: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+}
+
+impl Snapshot {
+	fn into_storage(self) -> Storage {
+		Storage {
+			top: self.top.into_iter().collect(),
+			children_default: self.children.into_iter().map(|(key, data)| {
+				let child = StorageChild {
+					data: data.into_iter().collect(),
+					child_info: ChildInfo::new_default(&key),
+				};
+				(key, child)
+			}).collect(),
+		}
+	}
+}
+
+/// Export the state at a block to a snapshot file.
+#[derive(Debug, StructOpt)]
+pub struct ExportSnapshotCmd {
+	/// Number of the block to export. Defaults to the best block.
+	#[structopt(long = "at")]
+	pub at: Option<BlockNumber>,
+
+	/// File to write the snapshot to.
+	#[structopt(long = "output", parse(from_os_str))]
+	pub output: PathBuf,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExportSnapshotCmd {
+	/// Write the state at the requested block to the output file.
+	pub fn run(&self, client: Arc<FullClient>) -> sc_cli::Result<()> {
+		let number = self.at.unwrap_or_else(|| client.info().best_number);
+		let hash = client.hash(number)?
+			.ok_or_else(|| format!("Block #{} is not in the database", number))?;
+
+		let storage = sc_service::chain_ops::export_raw_state(client, &BlockId::<Block>::Hash(hash))?;
+		let mut children = storage.children_default.into_iter()
+			.map(|(key, child)| (key, child.data.into_iter().collect()))
+			.collect::<Vec<_>>();
+		children.sort();
+		let snapshot = Snapshot {
+			number,
+			hash,
+			top: storage.top.into_iter().collect(),
+			children,
+		};
+
+		fs::write(&self.output, snapshot.encode())?;
+		println!(
+			"Exported {} keys and {} child tries of block #{} ({}) to {}",
+			snapshot.top.len(), snapshot.children.len(), number, hash, self.output.display(),
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportSnapshotCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// Dry-run a runtime upgrade against a snapshot.
+#[derive(Debug, StructOpt)]
+pub struct TryUpgradeCmd {
+	/// Snapshot written by `snapshot export`.
+	#[structopt(long = "snapshot", parse(from_os_str))]
+	pub snapshot: PathBuf,
+
+	/// The new runtime. It must be built with the `try-runtime` feature.
+	#[structopt(long = "wasm", parse(from_os_str))]
+	pub wasm: PathBuf,
+
+	/// Number of empty blocks to execute on top of the snapshot with the new runtime.
+	#[structopt(long = "blocks", default_value = "3")]
+	pub blocks: u32,
+
+	/// Print every changed storage key.
+	#[structopt(long = "verbose")]
+	pub verbose: bool,
+
+	/// Method for executing Wasm runtime code.
+	#[structopt(
+		long = "wasm-execution",
+		value_name = "METHOD",
+		possible_values = &WasmExecutionMethod::enabled_variants(),
+		case_insensitive = true,
+		default_value = "Interpreted"
+	)]
+	pub wasm_method: WasmExecutionMethod,
+}
+
+impl TryUpgradeCmd {
+	/// Run the upgrade and the blocks, reporting weight, storage changes and panics.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let snapshot = Snapshot::decode(&mut &fs::read(&self.snapshot)?[..])
+			.map_err(|e| format!("Invalid snapshot {}: {}", self.snapshot.display(), e))?;
+		let (number, hash) = (snapshot.number, snapshot.hash);
+		let before = snapshot.into_storage();
+
+		let code = fs::read(&self.wasm)?;
+		let runtime = DryRun {
+			executor: NativeExecutor::<Executor>::new(self.wasm_method.into(), None, 1),
+			wrapped_code: WrappedRuntimeCode(code.clone().into()),
+			code_hash: blake2_256(&code).to_vec(),
+		};
+		let mut storage = before.clone();
+		storage.top.insert(well_known_keys::CODE.to_vec(), code);
+
+		println!("Upgrading the runtime of block #{} ({})", number, hash);
+		let mut ext = BasicExternalities::new(storage.clone());
+		let (weight, max_weight) = runtime.call::<Result<(Weight, Weight), RuntimeString>>(
+			&mut ext, "TryRuntime_on_runtime_upgrade", &[],
+		)
+			.map_err(|e| format!(
+				"{}\nIs the runtime built with `--features try-runtime`?", e,
+			))?
+			.map_err(|e| format!("Migration check failed: {}", e))?;
+		println!(
+			"on_runtime_upgrade used {} weight ({}% of a block)",
+			weight, weight.saturating_mul(100) / max_weight.max(1),
+		);
+		report_changes(&before, &ext.into_storages(), self.verbose);
+
+		// Execute blocks from the same starting point, so the upgrade runs the way it will on
+		// chain: from `initialize_block` of the first block with the new `spec_version`.
+		let mut ext = BasicExternalities::new(storage);
+		let mut parent = (number, hash);
+		let mut timestamp = read_value::<u64>(&before, b"Timestamp", b"Now").unwrap_or_default();
+		for _ in 0..self.blocks {
+			timestamp += SLOT_DURATION;
+			let header = runtime.execute_empty_block(&mut ext, parent, timestamp)?;
+			println!("Executed block #{} ({})", header.number, header.hash());
+			parent = (header.number, header.hash());
+		}
+		report_changes(&before, &ext.into_storages(), self.verbose);
+
+		Ok(())
+	}
+}
+
+/// A runtime loaded from a Wasm blob, executed without a client or database.
+struct DryRun {
+	executor: NativeExecutor<Executor>,
+	wrapped_code: WrappedRuntimeCode<'static>,
+	code_hash: Vec<u8>,
+}
+
+impl DryRun {
+	/// Call a runtime API method in Wasm, turning a panic into an error.
+	fn call<R: Decode>(
+		&self,
+		ext: &mut BasicExternalities,
+		method: &str,
+		data: &[u8],
+	) -> Result<R, String> {
+		let runtime_code = RuntimeCode {
+			code_fetcher: &self.wrapped_code,
+			heap_pages: None,
+			hash: self.code_hash.clone(),
+		};
+		let result = self.executor.call::<NeverNativeValue, fn() -> Result<NeverNativeValue, String>>(
+			ext, &runtime_code, method, data, false, None,
+		).0.map_err(|e| format!("{} panicked: {}", method, e))?;
+
+		let encoded = match result {
+			NativeOrEncoded::Encoded(encoded) => encoded,
+			NativeOrEncoded::Native(_) => unreachable!("Native calls are never requested; qed"),
+		};
+		R::decode(&mut &encoded[..]).map_err(|e| format!("Invalid result of {}: {}", method, e))
+	}
+
+	/// Author a block containing only inherents on top of `parent`.
+	fn execute_empty_block(
+		&self,
+		ext: &mut BasicExternalities,
+		(parent_number, parent_hash): (BlockNumber, Hash),
+		timestamp: u64,
+	) -> Result<Header, String> {
+		let slot_number = timestamp / SLOT_DURATION;
+		let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 0,
+			slot_number,
+		});
+		let header = Header::new(
+			parent_number + 1,
+			Default::default(),
+			Default::default(),
+			parent_hash,
+			Digest { logs: vec![<DigestItem<Hash> as CompatibleDigestItem>::babe_pre_digest(pre_digest)] },
+		);
+		self.call::<()>(ext, "Core_initialize_block", &header.encode())?;
+
+		let mut inherent_data = InherentData::new();
+		inherent_data.put_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp)
+			.and_then(|_| inherent_data.put_data(sp_consensus_babe::inherents::INHERENT_IDENTIFIER, &slot_number))
+			.and_then(|_| inherent_data.put_data(sp_authorship::INHERENT_IDENTIFIER, &Vec::<Header>::new()))
+			.map_err(|e| format!("Failed to create inherent data: {:?}", e))?;
+		let inherents = self.call::<Vec<UncheckedExtrinsic>>(
+			ext, "BlockBuilder_inherent_extrinsics", &inherent_data.encode(),
+		)?;
+		for inherent in inherents {
+			self.call::<ApplyExtrinsicResult>(ext, "BlockBuilder_apply_extrinsic", &inherent.encode())?
+				.map_err(|e| format!("Inherent is invalid: {:?}", e))?
+				.map_err(|e| format!("Inherent failed: {:?}", e))?;
+		}
+
+		self.call::<Header>(ext, "BlockBuilder_finalize_block", &[])
+	}
+}
+
+/// Read and decode a `StorageValue` of a pallet.
+fn read_value<T: Decode>(storage: &Storage, pallet: &[u8], item: &[u8]) -> Option<T> {
+	let key = [twox_128(pallet), twox_128(item)].concat();
+	storage.top.get(&key).and_then(|value| T::decode(&mut &value[..]).ok())
+}
+
+/// Print how many keys were added, changed and removed in the main trie, and which if verbose.
+fn report_changes(before: &Storage, after: &Storage, verbose: bool) {
+	let mut changes = BTreeMap::new();
+	for (key, value) in &after.top {
+		match before.top.get(key) {
+			None => { changes.insert(key, "added"); },
+			Some(old) if old != value => { changes.insert(key, "changed"); },
+			_ => {},
+		}
+	}
+	for key in before.top.keys().filter(|key| !after.top.contains_key(*key)) {
+		changes.insert(key, "removed");
+	}
+
+	let count = |kind| changes.values().filter(|k| **k == kind).count();
+	println!(
+		"Storage: {} keys added, {} changed, {} removed",
+		count("added"), count("changed"), count("removed"),
+	);
+	if verbose {
+		for (key, kind) in changes {
+			println!("  {:<8} 0x{}", kind, hex_key(key));
+		}
+	}
+}
+
+fn hex_key(key: &[u8]) -> String {
+	key.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,114 @@
+//! GRANDPA voting rules of the node.
+
+use sc_finality_grandpa::VotingRule;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+use node_template_runtime::{BlockNumber, Hash, opaque::{Block, Header}};
+
+/// Never vote for a block less than the given number of blocks behind the best block, so that
+/// finality lags behind it by at least that much.
+#[derive(Clone)]
+pub struct BehindBestBlockBy(pub BlockNumber);
+
+impl<B: HeaderBackend<Block>> VotingRule<Block, B> for BehindBestBlockBy {
+	fn restrict_vote(
+		&self,
+		backend: &B,
+		base: &Header,
+		best_target: &Header,
+		current_target: &Header,
+	) -> Option<(Hash, BlockNumber)> {
+		let target_number = best_target.number().saturating_sub(self.0);
+
+		// our current vote is already far enough behind the best block
+		if target_number >= *current_target.number() {
+			return None;
+		}
+
+		// walk back from the current target, without going below what is already finalized
+		let target_number = target_number.max(*base.number());
+		let mut target_header = current_target.clone();
+		while *target_header.number() > target_number {
+			let parent_hash = *target_header.parent_hash();
+			target_header = match backend.header(BlockId::Hash(parent_hash)) {
+				Ok(Some(header)) => header,
+				Ok(None) => {
+					log::warn!("Not restricting the vote: header of {} is missing", parent_hash);
+					return None;
+				},
+				Err(e) => {
+					log::warn!("Not restricting the vote: failed to read header of {}: {}", parent_hash, e);
+					return None;
+				},
+			};
+		}
+
+		Some((target_header.hash(), *target_header.number()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+	use sp_blockchain::{BlockStatus, Info, Result};
+	use sp_runtime::{Digest, traits::NumberFor};
+
+	/// Headers of a single chain, looked up by hash.
+	struct Chain(HashMap<Hash, Header>);
+
+	impl HeaderBackend<Block> for Chain {
+		fn header(&self, id: BlockId<Block>) -> Result<Option<Header>> {
+			match id {
+				BlockId::Hash(hash) => Ok(self.0.get(&hash).cloned()),
+				BlockId::Number(_) => unimplemented!(),
+			}
+		}
+		fn info(&self) -> Info<Block> { unimplemented!() }
+		fn status(&self, _: BlockId<Block>) -> Result<BlockStatus> { unimplemented!() }
+		fn number(&self, _: Hash) -> Result<Option<NumberFor<Block>>> { unimplemented!() }
+		fn hash(&self, _: NumberFor<Block>) -> Result<Option<Hash>> { unimplemented!() }
+	}
+
+	/// Builds a chain of `len` blocks and returns it with its headers in order.
+	fn chain(len: BlockNumber) -> (Chain, Vec<Header>) {
+		let mut headers: Vec<Header> = Vec::new();
+		for number in 0..len {
+			let parent_hash = headers.last().map(|h| h.hash()).unwrap_or_default();
+			let header = Header::new(
+				number, Default::default(), Default::default(), parent_hash, Digest::default(),
+			);
+			headers.push(header);
+		}
+		(Chain(headers.iter().map(|h| (h.hash(), h.clone())).collect()), headers)
+	}
+
+	#[test]
+	fn votes_lag_behind_the_best_block() {
+		let (backend, headers) = chain(11);
+		let vote = BehindBestBlockBy(3).restrict_vote(&backend, &headers[0], &headers[10], &headers[10]);
+		assert_eq!(vote, Some((headers[7].hash(), 7)));
+	}
+
+	#[test]
+	fn votes_do_not_go_below_the_base() {
+		let (backend, headers) = chain(11);
+		let vote = BehindBestBlockBy(8).restrict_vote(&backend, &headers[5], &headers[10], &headers[10]);
+		assert_eq!(vote, Some((headers[5].hash(), 5)));
+	}
+
+	#[test]
+	fn votes_far_enough_behind_are_kept() {
+		let (backend, headers) = chain(11);
+		let vote = BehindBestBlockBy(3).restrict_vote(&backend, &headers[0], &headers[10], &headers[6]);
+		assert_eq!(vote, None);
+	}
+
+	#[test]
+	fn votes_are_kept_when_a_header_is_missing() {
+		let (mut backend, headers) = chain(11);
+		backend.0.remove(&headers[8].hash());
+		let vote = BehindBestBlockBy(3).restrict_vote(&backend, &headers[0], &headers[10], &headers[10]);
+		assert_eq!(vote, None);
+	}
+}
@@ -164,7 +164,7 @@ use sp_std::iter::FromIterator;
 use sp_std::slice;
 // use sp_std::iter::FusedIterator;
 // use sp_std::vec::Drain;
-use compare::Compare;
+use compare::{Compare, Rev};
 use sp_std::fmt;
 use sp_std::mem::{ size_of, swap};
 use sp_std::ptr;
@@ -293,6 +293,126 @@ where
     }
 }
 
+/// Marker for comparators whose result depends only on the two values being compared.
+///
+/// Heaps that live in runtime storage are rebuilt independently by every node, so any
+/// hidden input to the ordering (captured state, randomness, I/O) lets nodes disagree
+/// on the result. Closures given to `FnComparator` and `KeyComparator` can capture
+/// such state; only the non-capturing (`fn` pointer) forms implement this trait.
+///
+/// # Examples
+///
+/// ```
+/// use binary_heap_plus::*;
+/// use std::cmp::Ordering;
+///
+/// fn by_abs(a: &i32, b: &i32) -> Ordering {
+///     a.abs().cmp(&b.abs())
+/// }
+///
+/// let cmp = FnComparator(by_abs as fn(&i32, &i32) -> Ordering);
+/// let mut heap = BinaryHeap::from_vec_deterministic(vec![-7, 3, 5], cmp);
+/// assert_eq!(heap.pop(), Some(-7));
+/// ```
+pub trait DeterministicCompare<T>: Compare<T> {}
+
+impl<T: Ord> DeterministicCompare<T> for MaxComparator {}
+
+impl<T: Ord> DeterministicCompare<T> for MinComparator {}
+
+impl<T> DeterministicCompare<T> for FnComparator<fn(&T, &T) -> Ordering> {}
+
+impl<K: Ord, T> DeterministicCompare<T> for KeyComparator<fn(&T) -> K> {}
+
+impl<T, C: DeterministicCompare<T>> DeterministicCompare<T> for Rev<C> {}
+
+/// Comparators accepted by the constructors that take a caller-supplied comparator
+/// (`from_vec_cmp()`, `new_by()`, `new_by_key()` and their `with_capacity` forms).
+///
+/// With the `std` feature this is any `Compare`. Without it, i.e. when building a Wasm
+/// runtime, only [`DeterministicCompare`] comparators are accepted, so consensus code
+/// cannot order a heap with a capturing closure.
+///
+/// [`DeterministicCompare`]: trait.DeterministicCompare.html
+#[cfg(feature = "std")]
+pub trait CustomCompare<T>: Compare<T> {}
+
+#[cfg(feature = "std")]
+impl<T, C: Compare<T>> CustomCompare<T> for C {}
+
+/// Comparators accepted by the constructors that take a caller-supplied comparator
+/// (`from_vec_cmp()`, `new_by()`, `new_by_key()` and their `with_capacity` forms).
+///
+/// With the `std` feature this is any `Compare`. Without it, i.e. when building a Wasm
+/// runtime, only [`DeterministicCompare`] comparators are accepted, so consensus code
+/// cannot order a heap with a capturing closure.
+///
+/// [`DeterministicCompare`]: trait.DeterministicCompare.html
+#[cfg(not(feature = "std"))]
+pub trait CustomCompare<T>: DeterministicCompare<T> {}
+
+#[cfg(not(feature = "std"))]
+impl<T, C: DeterministicCompare<T>> CustomCompare<T> for C {}
+
+/// The comparator for fixed-point prices held in their raw integer form.
+///
+/// Built with `FixedPointComparator::new(decimals, precision)`: `decimals` is the number
+/// of decimal places the raw value carries (for example 18 for the inner `u128` of a
+/// `FixedU128`) and prices are ordered after truncating them to `precision` decimal
+/// places, so two prices within the same tick compare as equal. This will be a max-heap; use `Compare::rev()` for a min-heap.
+///
+/// # Examples
+///
+/// ```
+/// use binary_heap_plus::*;
+///
+/// // Raw prices with 4 decimals, ordered at 2 decimals: 1.2345 and 1.2399 are the same tick.
+/// let cmp = FixedPointComparator::new(4, 2);
+/// let mut heap = BinaryHeap::from_vec_deterministic(vec![1_2345u64, 1_2100, 1_2399], cmp);
+/// assert_eq!(heap.pop().map(|p| p / 100), Some(123));
+///
+/// // Asks are served lowest price first.
+/// use compare::Compare;
+/// let asks_cmp = Compare::<u64>::rev(cmp);
+/// let mut asks = BinaryHeap::from_vec_deterministic(vec![1_2345u64, 1_2100], asks_cmp);
+/// assert_eq!(asks.pop(), Some(1_2100));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FixedPointComparator {
+    tick: u128,
+}
+
+impl FixedPointComparator {
+    /// Creates a comparator for raw values with `decimals` decimal places, ordered at
+    /// `precision` decimal places.
+    pub fn new(decimals: u32, precision: u32) -> Self {
+        let tick = if precision >= decimals {
+            1
+        } else {
+            10u128.pow(decimals - precision)
+        };
+        FixedPointComparator { tick }
+    }
+
+    /// Size of one tick in raw units, i.e. `10^(decimals - precision)`.
+    pub fn tick(&self) -> u128 {
+        self.tick
+    }
+}
+
+impl<T> Compare<T> for FixedPointComparator
+where
+    T: Copy + Into<u128>,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        let (a, b): (u128, u128) = ((*a).into(), (*b).into());
+        (a / self.tick).cmp(&(b / self.tick))
+    }
+}
+
+impl<T> DeterministicCompare<T> for FixedPointComparator where T: Copy + Into<u128> {}
+
 /// Structure wrapping a mutable reference to the greatest item on a
 /// `BinaryHeap`.
 ///
@@ -384,19 +504,41 @@ impl<T, C: Compare<T> + Default> BinaryHeap<T, C> {
     /// Because `BinaryHeap` stores the elements in its internal `Vec`,
     /// it's natural to construct it from `Vec`.
     pub fn from_vec(vec: Vec<T>) -> Self {
-        BinaryHeap::from_vec_cmp(vec, C::default())
+        BinaryHeap::build(vec, C::default())
     }
 }
 
 impl<T, C: Compare<T>> BinaryHeap<T, C> {
+    fn build(vec: Vec<T>, cmp: C) -> Self {
+        let mut heap = BinaryHeap { data: vec, cmp };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T, C: CustomCompare<T>> BinaryHeap<T, C> {
     /// Generic constructor for `BinaryHeap` from `Vec` and comparator.
     ///
     /// Because `BinaryHeap` stores the elements in its internal `Vec`,
     /// it's natural to construct it from `Vec`.
+    ///
+    /// Without the `std` feature the comparator must be deterministic. See [`CustomCompare`].
+    ///
+    /// [`CustomCompare`]: trait.CustomCompare.html
     pub fn from_vec_cmp(vec: Vec<T>, cmp: C) -> Self {
-        let mut heap = BinaryHeap { data: vec, cmp };
-        heap.rebuild();
-        heap
+        BinaryHeap::build(vec, cmp)
+    }
+}
+
+impl<T, C: DeterministicCompare<T>> BinaryHeap<T, C> {
+    /// Constructor for `BinaryHeap` from `Vec` and a deterministic comparator.
+    ///
+    /// Same as `from_vec_cmp()`, but only accepts comparators that are safe to use
+    /// in consensus code. See [`DeterministicCompare`].
+    ///
+    /// [`DeterministicCompare`]: trait.DeterministicCompare.html
+    pub fn from_vec_deterministic(vec: Vec<T>, cmp: C) -> Self {
+        BinaryHeap::build(vec, cmp)
     }
 }
 
@@ -497,6 +639,7 @@ impl<T: Ord> BinaryHeap<T, MinComparator> {
 impl<T, F> BinaryHeap<T, FnComparator<F>>
 where
     F: Fn(&T, &T) -> Ordering,
+    FnComparator<F>: CustomCompare<T>,
 {
     /// Creates an empty `BinaryHeap`.
     ///
@@ -546,6 +689,7 @@ where
 impl<T, F, K: Ord> BinaryHeap<T, KeyComparator<F>>
 where
     F: Fn(&T) -> K,
+    KeyComparator<F>: CustomCompare<T>,
 {
     /// Creates an empty `BinaryHeap`.
     ///
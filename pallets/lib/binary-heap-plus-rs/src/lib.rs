@@ -92,6 +92,24 @@
 //! * `BinaryHeap::new_by()` creates a heap sorted by the given closure.
 //! * `BinaryHeap::new_by_key()` creates a heap sorted by the key generated by the given closure.
 //!
+//! ## Deterministic heaps
+//!
+//! Code that must produce the same result on every machine (e.g. a blockchain runtime)
+//! should build heaps with `BinaryHeap::from_vec_deterministic()`, which only accepts
+//! comparators implementing `DeterministicCompare`. Without the default `std` feature,
+//! `from_vec_cmp()`, `new_by()` and `new_by_key()` only accept those comparators too.
+//!
+//! ```
+//! use binary_heap_plus::*;
+//!
+//! let mut heap = BinaryHeap::from_vec_deterministic(vec![1, 5, 3], MinComparator);
+//! assert_eq!(heap.pop(), Some(1));
+//!
+//! // prices with 8 decimals, ordered at 2 decimals
+//! let mut bids = BinaryHeap::from_vec_deterministic(vec![1_00_000000u64, 1_01_000000], FixedPointComparator::new(8, 2));
+//! assert_eq!(bids.pop(), Some(1_01_000000));
+//! ```
+//!
 #![no_std]
 mod binary_heap;
 pub use crate::binary_heap::*;
@@ -415,6 +433,74 @@ mod from_liballoc {
     }
 }
 
+#[cfg(test)]
+mod tests_deterministic {
+    use super::binary_heap::*;
+    use compare::Compare;
+    use sp_std::cmp::Ordering;
+    use sp_std::vec;
+
+    fn assert_deterministic<T, C: DeterministicCompare<T>>(_: &C) {}
+
+    fn by_last_digit(a: &u32, b: &u32) -> Ordering {
+        (a % 10).cmp(&(b % 10))
+    }
+
+    fn last_digit(k: &u32) -> u32 {
+        k % 10
+    }
+
+    #[test]
+    fn builtin_comparators_are_deterministic() {
+        assert_deterministic::<u32, _>(&MaxComparator);
+        assert_deterministic::<u32, _>(&MinComparator);
+        assert_deterministic::<u32, _>(&FnComparator(by_last_digit as fn(&u32, &u32) -> Ordering));
+        assert_deterministic::<u32, _>(&KeyComparator(last_digit as fn(&u32) -> u32));
+        assert_deterministic::<u32, _>(&Compare::<u32>::rev(MaxComparator));
+        assert_deterministic::<u64, _>(&FixedPointComparator::new(18, 6));
+    }
+
+    #[test]
+    fn fn_pointer_comparators() {
+        let mut heap = BinaryHeap::from_vec_deterministic(
+            vec![21, 19, 33],
+            FnComparator(by_last_digit as fn(&u32, &u32) -> Ordering),
+        );
+        assert_eq!(heap.pop(), Some(19));
+
+        let mut heap = BinaryHeap::from_vec_deterministic(
+            vec![21, 19, 33],
+            KeyComparator(last_digit as fn(&u32) -> u32),
+        );
+        assert_eq!(heap.pop(), Some(19));
+    }
+
+    #[test]
+    fn fixed_point_comparator_orders_by_tick() {
+        assert_eq!(FixedPointComparator::new(18, 6).tick(), 1_000_000_000_000);
+        assert_eq!(FixedPointComparator::new(2, 2).tick(), 1);
+        assert_eq!(FixedPointComparator::new(2, 4).tick(), 1);
+
+        let cmp = FixedPointComparator::new(4, 2);
+        assert_eq!(cmp.compare(&1_2345u64, &1_2399u64), Ordering::Equal);
+        assert_eq!(cmp.compare(&1_2345u64, &1_2400u64), Ordering::Less);
+        assert_eq!(cmp.compare(&1_2500u64, &1_2400u64), Ordering::Greater);
+    }
+
+    #[test]
+    fn fixed_point_bids_and_asks() {
+        let prices = vec![1_0000u128, 9900, 1_0100, 1_0050];
+
+        let bids = BinaryHeap::from_vec_deterministic(prices.clone(), FixedPointComparator::new(4, 4));
+        assert_eq!(bids.into_iter_sorted().collect::<vec::Vec<_>>(), vec![1_0100, 1_0050, 1_0000, 9900]);
+
+        let asks_cmp = Compare::<u128>::rev(FixedPointComparator::new(4, 4));
+        let mut asks = BinaryHeap::from_vec_deterministic(prices, asks_cmp);
+        assert_eq!(asks.pop(), Some(9900));
+        assert_eq!(asks.pop(), Some(1_0000));
+    }
+}
+
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod tests_serde {
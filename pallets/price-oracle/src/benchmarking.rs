@@ -0,0 +1,101 @@
+//! Benchmarking setup for pallet-price-oracle
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{account, benchmarks};
+
+/// Largest number of oracles benchmarked with.
+const MAX_ORACLES: u32 = 100;
+
+/// Fill the oracle set with `n` accounts, returned sorted.
+fn set_oracles<T: Trait>(n: u32) -> Vec<T::AccountId> {
+	let mut oracles = (0 .. n).map(|i| account("oracle", i, 0)).collect::<Vec<T::AccountId>>();
+	oracles.sort();
+	Oracles::<T>::put(&oracles);
+	oracles
+}
+
+/// Generate a key of the crypto oracles sign with in the keystore.
+fn oracle_key<T: Trait>() -> T::Public {
+	type Keys<T> = <T as Trait>::AuthorityId;
+	let key = <Keys<T> as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::generate_pair(None);
+	<Keys<T> as AppCrypto<_, _>>::GenericPublic::from(key).into()
+}
+
+benchmarks! {
+	_ { }
+
+	// The signature is checked in `validate_unsigned`, so dispatching the report only stores it.
+	submit_prices_unsigned {
+		let n in 1 .. T::MaxPairs::get();
+		let prices = (0 .. n).map(|pair| (pair, 1_000 + pair as Price)).collect::<Vec<_>>();
+		let block_number = <frame_system::Module<T>>::block_number();
+		let payload = PricePayload { block_number, prices, public: oracle_key::<T>() };
+		let signature = SignedPayload::<T>::sign::<T::AuthorityId>(&payload)
+			.ok_or("Unable to sign the report")?;
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(
+			ReferencePrice::<T>::get(n - 1),
+			Some(PriceReport { price: 1_000 + (n - 1) as Price, at: block_number }),
+		);
+	}
+
+	add_oracle {
+		let n in 0 .. MAX_ORACLES - 1;
+		set_oracles::<T>(n);
+		let who: T::AccountId = account("new_oracle", 0, 0);
+	}: _(RawOrigin::Root, who.clone())
+	verify {
+		assert!(Module::<T>::oracles().contains(&who));
+	}
+
+	remove_oracle {
+		let n in 1 .. MAX_ORACLES;
+		let who = set_oracles::<T>(n).remove(0);
+	}: _(RawOrigin::Root, who.clone())
+	verify {
+		assert!(!Module::<T>::oracles().contains(&who));
+	}
+
+	register_pair {
+		let pair = PairId::max_value();
+		PairCount::put(T::MaxPairs::get() - 1);
+	}: _(RawOrigin::Root, pair)
+	verify {
+		assert!(TradingPairs::get(pair));
+	}
+
+	deregister_pair {
+		let pair = PairId::max_value();
+		TradingPairs::insert(pair, true);
+		PairCount::put(T::MaxPairs::get());
+	}: _(RawOrigin::Root, pair)
+	verify {
+		assert!(!TradingPairs::get(pair));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+	use sp_core::{testing::KeyStore, traits::KeystoreExt};
+
+	#[test]
+	fn test_benchmarks() {
+		let mut t = new_test_ext();
+		t.register_extension(KeystoreExt(KeyStore::new()));
+		t.execute_with(|| {
+			assert_ok!(test_benchmark_submit_prices_unsigned::<Test>());
+			assert_ok!(test_benchmark_add_oracle::<Test>());
+			assert_ok!(test_benchmark_remove_oracle::<Test>());
+			assert_ok!(test_benchmark_register_pair::<Test>());
+			assert_ok!(test_benchmark_deregister_pair::<Test>());
+		});
+	}
+}
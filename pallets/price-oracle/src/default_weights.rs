@@ -0,0 +1,39 @@
+//! Default weights for pallet-price-oracle, used by runtimes that configure `WeightInfo = ()`.
+//!
+//! Estimates written by hand, not benchmarked. Runtimes should prefer the weights generated
+//! against their own configuration with the node's `benchmark` subcommand.
+
+#![allow(unused_parens)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+impl crate::WeightInfo for () {
+	fn submit_prices_unsigned(n: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn add_oracle(n: u32) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_oracle(n: u32) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn register_pair() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn deregister_pair() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,473 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Reference prices of traded pairs, computed by offchain workers, for trigger orders and margin
+//! checks.
+//!
+//! Fills are noted on-chain with [`Module::note_fill`], usually through [`OnFill`] by a matching
+//! engine, and handed to offchain workers through offchain indexing, so nodes must run with
+//! `--enable-offchain-indexing true`. Once every `UnsignedInterval` blocks, an offchain worker
+//! computes the volume weighted average price (VWAP) of the fills of the last `FillWindow` blocks
+//! and reports it in an unsigned transaction.
+//!
+//! Reports carry a payload signed with a key of `AuthorityId`, inserted into the keystore of the
+//! node with `author_insertKey`. Runtimes use `node_template_primitives::oracle::crypto::OracleId`,
+//! of key type `orcl`. Only reports signed by the accounts in [`Oracles`], managed
+//! by `OracleOrigin`, are accepted.
+//!
+//! Prices are only reported for the pairs in the trading-pair registry, [`TradingPairs`], managed
+//! by `PairOrigin`.
+
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, debug, ensure,
+	traits::{EnsureOrigin, Get}, weights::Weight, StorageValue, StorageMap,
+};
+use frame_system::{
+	ensure_none,
+	offchain::{
+		AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+		SigningTypes,
+	},
+};
+use sp_core::U256;
+use sp_runtime::{
+	RuntimeAppPublic,
+	offchain::storage::StorageValueRef,
+	traits::{IdentifyAccount, One, Saturating},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+		ValidTransaction,
+	},
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+mod default_weights;
+pub mod migrations;
+
+/// Identifier of a traded pair.
+pub type PairId = u32;
+
+/// Price of one unit of the base asset of a pair, in units of its quote asset.
+pub type Price = u128;
+
+/// Weight functions needed for this pallet.
+pub trait WeightInfo {
+	fn submit_prices_unsigned(n: u32) -> Weight;
+	fn add_oracle(n: u32) -> Weight;
+	fn remove_oracle(n: u32) -> Weight;
+	fn register_pair() -> Weight;
+	fn deregister_pair() -> Weight;
+}
+
+/// A trade between two orders of a pair.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Fill {
+	pub pair: PairId,
+	pub price: Price,
+	/// Traded amount of the base asset.
+	pub amount: u128,
+}
+
+/// Handler of the fills of a matching engine, so that it does not depend on this pallet.
+pub trait OnFill {
+	fn on_fill(fill: Fill);
+}
+
+impl OnFill for () {
+	fn on_fill(_: Fill) {}
+}
+
+/// A reference price and the block it was computed at.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PriceReport<BlockNumber> {
+	pub price: Price,
+	pub at: BlockNumber,
+}
+
+/// Storage layouts of this pallet. A new variant is added with every storage migration.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Releases {
+	/// First release, with signed reports from the oracle set.
+	V1_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+impl Releases {
+	/// The layout this version of the pallet reads and writes.
+	pub const CURRENT: Releases = Releases::V1_0_0;
+}
+
+/// Prices computed by an oracle, signed with its key.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct PricePayload<Public, BlockNumber> {
+	/// Block the prices were computed at.
+	pub block_number: BlockNumber,
+	pub prices: Vec<(PairId, Price)>,
+	/// Key the payload is signed with.
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for PricePayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+pub trait Trait: frame_system::Trait + CreateSignedTransaction<Call<Self>> {
+	/// Because this pallet emits events, it depends on the runtime's definition of an event.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+	/// Crypto of the keys oracles sign their reports with.
+	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+	/// Origin allowed to add and remove oracles.
+	type OracleOrigin: EnsureOrigin<Self::Origin>;
+	/// Origin allowed to register and deregister trading pairs.
+	type PairOrigin: EnsureOrigin<Self::Origin>;
+	/// Maximum number of registered trading pairs, and so of prices in a report.
+	type MaxPairs: Get<u32>;
+	/// Number of blocks whose fills are averaged into a reference price. Must be below the
+	/// `BlockHashCount` of the system pallet, which the fills are looked up with.
+	type FillWindow: Get<Self::BlockNumber>;
+	/// Minimum number of blocks between two price reports.
+	type UnsignedInterval: Get<Self::BlockNumber>;
+	/// Priority of price reports in the transaction pool.
+	type UnsignedPriority: Get<TransactionPriority>;
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as PriceOracle {
+		/// Latest reference price of every pair that has been traded.
+		pub ReferencePrice get(fn reference_price):
+			map hasher(twox_64_concat) PairId => Option<PriceReport<T::BlockNumber>>;
+		/// Fills of the last block, moved to offchain storage when the next one is initialized and
+		/// its hash is known.
+		BlockFills: Vec<Fill>;
+		/// First block whose price report is accepted.
+		pub NextUnsignedAt get(fn next_unsigned_at): T::BlockNumber;
+		/// Accounts whose price reports are accepted, sorted.
+		pub Oracles get(fn oracles) config(): Vec<T::AccountId>;
+		/// Pairs that are traded, and whose reference prices are reported.
+		pub TradingPairs get(fn is_trading_pair): map hasher(twox_64_concat) PairId => bool;
+		/// Number of pairs in `TradingPairs`.
+		pub PairCount get(fn pair_count): u32;
+		/// Storage layout in use, checked by migrations.
+		StorageVersion get(fn storage_version) build(|_| Releases::CURRENT): Releases;
+	}
+	add_extra_genesis {
+		config(trading_pairs): Vec<PairId>;
+		build(|config| {
+			let mut oracles = config.oracles.clone();
+			oracles.sort();
+			oracles.dedup();
+			Oracles::<T>::put(oracles);
+			for pair in &config.trading_pairs {
+				if !TradingPairs::get(pair) {
+					TradingPairs::insert(pair, true);
+					PairCount::mutate(|count| *count += 1);
+				}
+			}
+			assert!(PairCount::get() <= T::MaxPairs::get(), "More trading pairs than `MaxPairs`");
+		});
+	}
+}
+
+decl_event!(
+	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+		/// The reference price of a pair was updated. [pair, price]
+		PriceReported(PairId, Price),
+		/// An account may now report prices. [oracle]
+		OracleAdded(AccountId),
+		/// An account may no longer report prices. [oracle]
+		OracleRemoved(AccountId),
+		/// A pair was added to the trading-pair registry. [pair]
+		PairRegistered(PairId),
+		/// A pair was removed from the trading-pair registry. [pair]
+		PairDeregistered(PairId),
+	}
+);
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// The account is already an oracle.
+		AlreadyOracle,
+		/// The account is not an oracle.
+		NotOracle,
+		/// The pair is already registered.
+		AlreadyRegistered,
+		/// The pair is not registered.
+		NotRegistered,
+		/// `MaxPairs` pairs are already registered.
+		TooManyPairs,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Record the reference prices computed by an oracle.
+		///
+		/// The signature of the payload is checked in `validate_unsigned`.
+		#[weight = T::WeightInfo::submit_prices_unsigned(payload.prices.len() as u32)]
+		pub fn submit_prices_unsigned(
+			origin,
+			payload: PricePayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> dispatch::DispatchResult {
+			ensure_none(origin)?;
+			Self::store_prices(payload.block_number, payload.prices);
+			Ok(())
+		}
+
+		/// Accept the price reports of `who`.
+		#[weight = T::WeightInfo::add_oracle(Oracles::<T>::decode_len().unwrap_or(0) as u32)]
+		pub fn add_oracle(origin, who: T::AccountId) -> dispatch::DispatchResult {
+			T::OracleOrigin::ensure_origin(origin)?;
+
+			let mut oracles = Self::oracles();
+			let index = oracles.binary_search(&who).err().ok_or(Error::<T>::AlreadyOracle)?;
+			oracles.insert(index, who.clone());
+			Oracles::<T>::put(oracles);
+
+			Self::deposit_event(RawEvent::OracleAdded(who));
+			Ok(())
+		}
+
+		/// Stop accepting the price reports of `who`.
+		#[weight = T::WeightInfo::remove_oracle(Oracles::<T>::decode_len().unwrap_or(0) as u32)]
+		pub fn remove_oracle(origin, who: T::AccountId) -> dispatch::DispatchResult {
+			T::OracleOrigin::ensure_origin(origin)?;
+
+			let mut oracles = Self::oracles();
+			let index = oracles.binary_search(&who).ok().ok_or(Error::<T>::NotOracle)?;
+			oracles.remove(index);
+			Oracles::<T>::put(oracles);
+
+			Self::deposit_event(RawEvent::OracleRemoved(who));
+			Ok(())
+		}
+
+		/// Add `pair` to the trading-pair registry.
+		#[weight = T::WeightInfo::register_pair()]
+		pub fn register_pair(origin, pair: PairId) -> dispatch::DispatchResult {
+			T::PairOrigin::ensure_origin(origin)?;
+			ensure!(!TradingPairs::get(pair), Error::<T>::AlreadyRegistered);
+			ensure!(Self::pair_count() < T::MaxPairs::get(), Error::<T>::TooManyPairs);
+
+			TradingPairs::insert(pair, true);
+			PairCount::mutate(|count| *count += 1);
+			Self::deposit_event(RawEvent::PairRegistered(pair));
+			Ok(())
+		}
+
+		/// Remove `pair` from the trading-pair registry. Its last reference price is kept.
+		#[weight = T::WeightInfo::deregister_pair()]
+		pub fn deregister_pair(origin, pair: PairId) -> dispatch::DispatchResult {
+			T::PairOrigin::ensure_origin(origin)?;
+			ensure!(TradingPairs::get(pair), Error::<T>::NotRegistered);
+
+			TradingPairs::remove(pair);
+			PairCount::mutate(|count| *count = count.saturating_sub(1));
+			Self::deposit_event(RawEvent::PairDeregistered(pair));
+			Ok(())
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// The fills of the last block are keyed by its hash, so that the fills of sibling forks
+			// do not overwrite each other.
+			let fills = BlockFills::take();
+			if !fills.is_empty() {
+				let parent_hash = <frame_system::Module<T>>::parent_hash();
+				let key = fills_key(now.saturating_sub(One::one()), parent_hash);
+				sp_io::offchain_index::set(&key, &fills.encode());
+			}
+			// Fills older than the window are never read again. Those of abandoned forks are left
+			// behind, as their hashes are not known here.
+			let expired = now.saturating_sub(T::FillWindow::get());
+			let expired_hash = <frame_system::Module<T>>::block_hash(expired);
+			sp_io::offchain_index::clear(&fills_key(expired, expired_hash));
+
+			T::DbWeight::get().reads_writes(3, 1)
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::report_prices(now) {
+				debug::warn!("Failed to report prices at block {:?}: {}", now, e);
+			}
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Note a fill of the current block, to be averaged into the reference price of its pair.
+	pub fn note_fill(fill: Fill) {
+		BlockFills::append(fill);
+	}
+
+	/// Add `fills` to the total value and amount traded of their pairs, or to `None` once the
+	/// totals of a pair overflow.
+	fn add_fills(totals: &mut BTreeMap<PairId, Option<(U256, U256)>>, fills: Vec<Fill>) {
+		for fill in fills {
+			let total = totals.entry(fill.pair).or_insert_with(|| Some(Default::default()));
+			*total = (*total).and_then(|(value, amount)| Some((
+				value.checked_add(U256::from(fill.price).checked_mul(U256::from(fill.amount))?)?,
+				amount.checked_add(U256::from(fill.amount))?,
+			)));
+		}
+	}
+
+	fn store_prices(block_number: T::BlockNumber, prices: Vec<(PairId, Price)>) {
+		for (pair, price) in prices {
+			ReferencePrice::<T>::insert(pair, PriceReport { price, at: block_number });
+			Self::deposit_event(RawEvent::PriceReported(pair, price));
+		}
+		<NextUnsignedAt<T>>::put(block_number.saturating_add(T::UnsignedInterval::get()));
+	}
+
+	fn is_oracle(public: &T::Public) -> bool {
+		Self::oracles().binary_search(&public.clone().into_account()).is_ok()
+	}
+
+	/// Volume weighted average price of every pair traded in the `FillWindow` blocks up to `now`.
+	///
+	/// The fills of `now` are read from `BlockFills`, and those of the blocks before it from
+	/// offchain storage, under the hashes of the chain `now` is on. Pairs whose traded value
+	/// overflows are skipped.
+	pub fn vwap(now: T::BlockNumber) -> Vec<(PairId, Price)> {
+		let mut totals = BTreeMap::new();
+		let mut block = now.saturating_sub(T::FillWindow::get()).saturating_add(One::one());
+		while block < now {
+			let key = fills_key(block, <frame_system::Module<T>>::block_hash(block));
+			if let Some(Some(fills)) = StorageValueRef::persistent(&key).get::<Vec<Fill>>() {
+				Self::add_fills(&mut totals, fills);
+			}
+			block += One::one();
+		}
+		Self::add_fills(&mut totals, BlockFills::get());
+
+		totals.into_iter()
+			.filter_map(|(pair, total)| match total {
+				// The average is never above the highest price, which fits in a `u128`.
+				Some((value, amount)) if !amount.is_zero() => Some((pair, (value / amount).low_u128())),
+				Some(_) => None,
+				None => {
+					debug::warn!("Value traded in pair {} overflows, not reporting its price", pair);
+					None
+				}
+			})
+			.collect()
+	}
+
+	fn report_prices(now: T::BlockNumber) -> Result<(), &'static str> {
+		if now < Self::next_unsigned_at() {
+			return Ok(());
+		}
+		let mut prices = Self::vwap(now);
+		prices.retain(|(pair, _)| TradingPairs::get(pair));
+		if prices.is_empty() {
+			return Ok(());
+		}
+
+		// Sign with a local key of an oracle, if this node has one.
+		type Keys<T> = <T as Trait>::AuthorityId;
+		let oracle_keys = <Keys<T> as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+			.into_iter()
+			.map(|key| <Keys<T> as AppCrypto<_, _>>::GenericPublic::from(key).into())
+			.filter(Self::is_oracle)
+			.collect::<Vec<T::Public>>();
+		if oracle_keys.is_empty() {
+			return Ok(());
+		}
+
+		let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+			.with_filter(oracle_keys)
+			.send_unsigned_transaction(
+				|account| PricePayload {
+					block_number: now,
+					prices: prices.clone(),
+					public: account.public.clone(),
+				},
+				|payload, signature| Call::submit_prices_unsigned(payload, signature),
+			)
+			.ok_or("No oracle key in the keystore")?;
+		result.map_err(|()| "Unable to submit unsigned transaction")
+	}
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		let (payload, signature) = match call {
+			Call::submit_prices_unsigned(payload, signature) => (payload, signature),
+			_ => return InvalidTransaction::Call.into(),
+		};
+
+		// Only oracles may set reference prices.
+		if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+			return InvalidTransaction::BadProof.into();
+		}
+		if !Self::is_oracle(&payload.public) {
+			return InvalidTransaction::BadProof.into();
+		}
+		let PricePayload { block_number, prices, .. } = payload;
+
+		// Only one report per interval is accepted, computed at a block that was imported.
+		let next_unsigned_at = Self::next_unsigned_at();
+		if *block_number < next_unsigned_at {
+			return InvalidTransaction::Stale.into();
+		}
+		if *block_number > <frame_system::Module<T>>::block_number() {
+			return InvalidTransaction::Future.into();
+		}
+		// Prices are listed by ascending pair, once per registered pair.
+		if prices.is_empty()
+			|| prices.len() > T::MaxPairs::get() as usize
+			|| prices.windows(2).any(|pairs| pairs[0].0 >= pairs[1].0)
+			|| prices.iter().any(|(pair, _)| !TradingPairs::get(pair))
+		{
+			return InvalidTransaction::Call.into();
+		}
+
+		ValidTransaction::with_tag_prefix("PriceOracle")
+			.priority(T::UnsignedPriority::get())
+			// Reports for the same interval share this tag and priority, so the pool keeps the first
+			// one it sees and rejects the others.
+			.and_provides(next_unsigned_at)
+			.longevity(5)
+			.propagate(true)
+			.build()
+	}
+}
+
+impl<T: Trait> OnFill for Module<T> {
+	fn on_fill(fill: Fill) {
+		Self::note_fill(fill)
+	}
+}
+
+/// Offchain storage key of the fills of a block.
+fn fills_key<BlockNumber: Encode, Hash: Encode>(block: BlockNumber, hash: Hash) -> Vec<u8> {
+	let mut key = b"price-oracle::fills::".to_vec();
+	(block, hash).encode_to(&mut key);
+	key
+}
@@ -0,0 +1,42 @@
+//! Storage migrations of the price oracle pallet.
+//!
+//! Every migration moves `StorageVersion` one release forward and does nothing when the state is
+//! already past it, so running `migrate` on every runtime upgrade is safe. There is none yet: the
+//! pallet records its first release, `V1_0_0`, at genesis.
+
+use frame_support::{traits::Get, weights::Weight};
+#[cfg(feature = "try-runtime")]
+use frame_support::{IterableStorageMap, StorageValue};
+use crate::Trait;
+#[cfg(feature = "try-runtime")]
+use crate::{Module, PairCount, Releases, StorageVersion, TradingPairs};
+
+/// Run all pending migrations and return the weight they consumed.
+pub fn migrate<T: Trait>() -> Weight {
+	T::DbWeight::get().reads(1)
+}
+
+/// Check that the state can be migrated. Run against live state before an upgrade is proposed.
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate<T: Trait>() -> Result<(), &'static str> {
+	if StorageVersion::get() > Releases::CURRENT {
+		return Err("Price oracle storage is newer than this runtime");
+	}
+	Ok(())
+}
+
+/// Check that the state is in the layout this runtime expects after `migrate`.
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Trait>() -> Result<(), &'static str> {
+	if StorageVersion::get() != Releases::CURRENT {
+		return Err("Price oracle storage was not migrated to the current version");
+	}
+	let oracles = Module::<T>::oracles();
+	if oracles.windows(2).any(|pair| pair[0] >= pair[1]) {
+		return Err("Price oracles are not sorted and unique");
+	}
+	if TradingPairs::iter().count() as u32 != PairCount::get() {
+		return Err("Price oracle pair count does not match the registry");
+	}
+	Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::{Call, Module, Trait};
+use primitives::oracle::crypto;
+use sp_core::{H256, sr25519};
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes};
+use sp_runtime::{
+	traits::{BlakeTwo256, IdentityLookup, Extrinsic as ExtrinsicT}, testing::{Header, TestXt},
+	transaction_validity::TransactionPriority, Perbill,
+};
+use frame_system as system;
+
+impl_outer_origin! {
+	pub enum Origin for Test {}
+}
+
+// Configure a mock runtime to test the pallet.
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl system::Trait for Test {
+	type BaseCallFilter = ();
+	type Origin = Origin;
+	type Call = ();
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = sr25519::Public;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumExtrinsicWeight = MaximumBlockWeight;
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+}
+
+/// Unsigned extrinsics submitted by the offchain worker, as found in the test transaction pool.
+pub type Extrinsic = TestXt<Call<Test>, ()>;
+
+impl SigningTypes for Test {
+	type Public = sr25519::Public;
+	type Signature = sr25519::Signature;
+}
+
+impl<C> SendTransactionTypes<C> for Test where Call<Test>: From<C> {
+	type OverarchingCall = Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+impl<C> CreateSignedTransaction<C> for Test where Call<Test>: From<C> {
+	fn create_transaction<A: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call<Test>,
+		_public: Self::Public,
+		_account: Self::AccountId,
+		nonce: u64,
+	) -> Option<(Call<Test>, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+parameter_types! {
+	pub const MaxPairs: u32 = 16;
+	pub const FillWindow: u64 = 3;
+	pub const UnsignedInterval: u64 = 2;
+	pub const UnsignedPriority: TransactionPriority = 1 << 20;
+}
+
+impl Trait for Test {
+	type Event = ();
+	type AuthorityId = crypto::OracleId;
+	type OracleOrigin = system::EnsureRoot<sr25519::Public>;
+	type PairOrigin = system::EnsureRoot<sr25519::Public>;
+	type MaxPairs = MaxPairs;
+	type FillWindow = FillWindow;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = ();
+}
+
+pub type System = system::Module<Test>;
+pub type PriceOracle = Module<Test>;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		oracles: vec![],
+		trading_pairs: vec![0, 1, 2, 3],
+	}.assimilate_storage(&mut storage).unwrap();
+	storage.into()
+}
@@ -0,0 +1,369 @@
+use crate::{
+	Call, Error, Fill, PricePayload, PriceReport, ReferencePrice, BlockFills, NextUnsignedAt,
+	Oracles, Releases, fills_key, migrations, mock::*,
+};
+use primitives::oracle::{KEY_TYPE, crypto};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_ok, assert_noop, storage::{StorageMap, StorageValue},
+	traits::{Get, OffchainWorker, OnFinalize, OnInitialize, UnfilteredDispatchable},
+	unsigned::ValidateUnsigned,
+};
+use frame_system::offchain::SignedPayload;
+use sp_core::{
+	offchain::{
+		OffchainExt, StorageKind, TransactionPoolExt,
+		testing::{PoolState, TestOffchainExt, TestTransactionPoolExt},
+	},
+	sr25519, testing::KeyStore, traits::KeystoreExt, H256,
+};
+use sp_runtime::{
+	DispatchError,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
+use std::sync::Arc;
+use parking_lot::RwLock;
+
+/// Test externalities with an offchain database and a transaction pool.
+fn new_offchain_test_ext() -> (sp_io::TestExternalities, Arc<RwLock<PoolState>>) {
+	let (offchain, _state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	(t, pool_state)
+}
+
+/// Register a keystore holding a new `orcl` key, returned, and make it an oracle if `authorized`.
+fn add_oracle_key(t: &mut sp_io::TestExternalities, authorized: bool) -> sr25519::Public {
+	let keystore = KeyStore::new();
+	let oracle = keystore.write().sr25519_generate_new(KEY_TYPE, None).unwrap();
+	t.register_extension(KeystoreExt(keystore));
+	if authorized {
+		t.execute_with(|| Oracles::<Test>::put(vec![oracle]));
+	}
+	oracle
+}
+
+/// A price report signed with the key of `oracle`, found in the keystore.
+fn signed_report(
+	oracle: sr25519::Public,
+	block_number: u64,
+	prices: Vec<(u32, u128)>,
+) -> Call<Test> {
+	let payload = PricePayload { block_number, prices, public: oracle };
+	let signature = SignedPayload::<Test>::sign::<crypto::OracleId>(&payload).unwrap();
+	Call::submit_prices_unsigned(payload, signature)
+}
+
+/// Hash of `block` in the tests.
+fn block_hash(block: u64) -> H256 {
+	H256::repeat_byte(block as u8)
+}
+
+/// Store the fills of a block of the chain the way offchain indexing does.
+fn index_fills(block: u64, fills: &[Fill]) {
+	frame_system::BlockHash::<Test>::insert(block, block_hash(block));
+	index_fork_fills(block, block_hash(block), fills);
+}
+
+/// Store the fills of a block that may not be on the chain.
+fn index_fork_fills(block: u64, hash: H256, fills: &[Fill]) {
+	let key = fills_key(block, hash);
+	sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &key, &fills.encode());
+}
+
+fn fill(pair: u32, price: u128, amount: u128) -> Fill {
+	Fill { pair, price, amount }
+}
+
+#[test]
+fn vwap_weighs_fills_of_the_window_by_amount() {
+	let (mut t, _) = new_offchain_test_ext();
+	t.execute_with(|| {
+		// Outside of the three block window of block 10.
+		index_fills(7, &[fill(0, 1_000, 100)]);
+		index_fills(8, &[fill(0, 100, 1), fill(1, 50, 4)]);
+		index_fills(9, &[fill(0, 130, 2), fill(1, 60, 0)]);
+		// On a sibling of block 9.
+		index_fork_fills(9, H256::repeat_byte(0xff), &[fill(0, 1, 1_000)]);
+		// Not indexed until the next block.
+		BlockFills::put(vec![fill(1, 70, 1)]);
+
+		// (100 * 1 + 130 * 2) / 3 and (50 * 4 + 70 * 1) / 5.
+		assert_eq!(PriceOracle::vwap(10), vec![(0, 120), (1, 54)]);
+		BlockFills::kill();
+		assert_eq!(PriceOracle::vwap(11), vec![(0, 130)]);
+		assert_eq!(PriceOracle::vwap(20), vec![]);
+	});
+}
+
+#[test]
+fn vwap_does_not_overflow() {
+	let (mut t, _) = new_offchain_test_ext();
+	t.execute_with(|| {
+		let max = u128::max_value();
+		index_fills(1, &[fill(0, max, max), fill(0, max, 1), fill(1, max, max)]);
+		assert_eq!(PriceOracle::vwap(2), vec![(0, max), (1, max)]);
+
+		// The value traded in pair 0 no longer fits in 256 bits, so it is skipped.
+		index_fills(2, &[fill(0, max, max), fill(1, 1, 1)]);
+		// (max * max + 1) / (max + 1).
+		assert_eq!(PriceOracle::vwap(3), vec![(1, max - 1)]);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_signed_price_report() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	let oracle = add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		index_fills(3, &[fill(3, 200, 1), fill(3, 100, 3)]);
+
+		PriceOracle::offchain_worker(4);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		match tx.call {
+			Call::submit_prices_unsigned(payload, signature) => {
+				assert_eq!(
+					payload,
+					PricePayload { block_number: 4, prices: vec![(3, 125)], public: oracle },
+				);
+				assert!(SignedPayload::<Test>::verify::<crypto::OracleId>(&payload, signature));
+			}
+			call => panic!("Unexpected call {:?}", call),
+		}
+	});
+}
+
+#[test]
+fn offchain_worker_needs_an_oracle_key() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	t.execute_with(|| index_fills(3, &[fill(3, 200, 1)]));
+
+	// No key in the keystore.
+	t.execute_with(|| PriceOracle::offchain_worker(4));
+	assert!(pool_state.read().transactions.is_empty());
+
+	// A key that is not an oracle.
+	add_oracle_key(&mut t, false);
+	t.execute_with(|| PriceOracle::offchain_worker(4));
+	assert!(pool_state.read().transactions.is_empty());
+}
+
+#[test]
+fn offchain_worker_only_reports_registered_pairs() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		index_fills(3, &[fill(3, 200, 1), fill(4, 100, 3)]);
+
+		PriceOracle::offchain_worker(4);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		match Extrinsic::decode(&mut &*tx).unwrap().call {
+			Call::submit_prices_unsigned(payload, _) => assert_eq!(payload.prices, vec![(3, 200)]),
+			call => panic!("Unexpected call {:?}", call),
+		}
+
+		// Nothing is reported when no traded pair is registered.
+		index_fills(7, &[fill(4, 100, 3)]);
+		PriceOracle::offchain_worker(8);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_waits_for_the_next_interval() {
+	let (mut t, pool_state) = new_offchain_test_ext();
+	add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		index_fills(3, &[fill(3, 200, 1)]);
+		NextUnsignedAt::<Test>::put(5);
+
+		PriceOracle::offchain_worker(4);
+		assert!(pool_state.read().transactions.is_empty());
+
+		// Nothing is reported when nothing was traded.
+		PriceOracle::offchain_worker(8);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn price_reports_update_reference_prices() {
+	let mut t = new_test_ext();
+	let oracle = add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		assert_ok!(signed_report(oracle, 4, vec![(0, 10), (1, 20)]).dispatch_bypass_filter(Origin::none()));
+
+		assert_eq!(PriceOracle::reference_price(0), Some(PriceReport { price: 10, at: 4 }));
+		assert_eq!(PriceOracle::reference_price(1), Some(PriceReport { price: 20, at: 4 }));
+		assert_eq!(PriceOracle::reference_price(2), None);
+		assert_eq!(PriceOracle::next_unsigned_at(), 6);
+	});
+}
+
+#[test]
+fn price_reports_must_be_unsigned() {
+	let mut t = new_test_ext();
+	let oracle = add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		assert_noop!(
+			signed_report(oracle, 4, vec![(0, 10)]).dispatch_bypass_filter(Origin::signed(oracle)),
+			DispatchError::BadOrigin
+		);
+		assert!(!ReferencePrice::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn validate_unsigned_accepts_one_report_per_interval() {
+	let mut t = new_test_ext();
+	let oracle = add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		System::set_block_number(5);
+		NextUnsignedAt::<Test>::put(4);
+		let validate = |block_number, prices| PriceOracle::validate_unsigned(
+			TransactionSource::External,
+			&signed_report(oracle, block_number, prices),
+		);
+
+		let valid = validate(5, vec![(0, 10)]).unwrap();
+		assert_eq!(valid.priority, UnsignedPriority::get());
+		assert_eq!(valid.provides, vec![("PriceOracle", 4u64).encode()]);
+		assert_eq!(validate(4, vec![(0, 10)]).unwrap().provides, valid.provides);
+
+		assert_eq!(validate(3, vec![(0, 10)]), InvalidTransaction::Stale.into());
+		assert_eq!(validate(6, vec![(0, 10)]), InvalidTransaction::Future.into());
+		assert_eq!(validate(5, vec![]), InvalidTransaction::Call.into());
+		assert_eq!(validate(5, vec![(0, 10), (4, 10)]), InvalidTransaction::Call.into());
+		assert_eq!(validate(5, vec![(0, 10), (0, 20)]), InvalidTransaction::Call.into());
+		assert_eq!(validate(5, vec![(1, 10), (0, 20)]), InvalidTransaction::Call.into());
+		let too_many = (0 .. MaxPairs::get() + 1).map(|pair| (pair, 10)).collect();
+		assert_eq!(validate(5, too_many), InvalidTransaction::Call.into());
+	});
+}
+
+#[test]
+fn validate_unsigned_rejects_reports_not_signed_by_an_oracle() {
+	let mut t = new_test_ext();
+	let oracle = add_oracle_key(&mut t, true);
+	t.execute_with(|| {
+		System::set_block_number(5);
+		let validate = |call| PriceOracle::validate_unsigned(TransactionSource::External, &call);
+
+		// Tampered with after signing.
+		let call = match signed_report(oracle, 5, vec![(0, 10)]) {
+			Call::submit_prices_unsigned(mut payload, signature) => {
+				payload.prices = vec![(0, 1)];
+				Call::submit_prices_unsigned(payload, signature)
+			}
+			call => call,
+		};
+		assert_eq!(validate(call), InvalidTransaction::BadProof.into());
+
+		// Correctly signed by a removed oracle.
+		assert_ok!(PriceOracle::remove_oracle(Origin::root(), oracle));
+		assert_eq!(
+			validate(signed_report(oracle, 5, vec![(0, 10)])),
+			InvalidTransaction::BadProof.into(),
+		);
+	});
+}
+
+#[test]
+fn oracles_are_managed_by_oracle_origin() {
+	new_test_ext().execute_with(|| {
+		let oracle = sr25519::Public::from_raw([1; 32]);
+		let other = sr25519::Public::from_raw([2; 32]);
+
+		assert_noop!(PriceOracle::add_oracle(Origin::signed(oracle), oracle), DispatchError::BadOrigin);
+		assert_ok!(PriceOracle::add_oracle(Origin::root(), other));
+		assert_ok!(PriceOracle::add_oracle(Origin::root(), oracle));
+		assert_eq!(PriceOracle::oracles(), vec![oracle, other]);
+		assert_noop!(PriceOracle::add_oracle(Origin::root(), oracle), Error::<Test>::AlreadyOracle);
+
+		assert_noop!(PriceOracle::remove_oracle(Origin::signed(oracle), oracle), DispatchError::BadOrigin);
+		assert_ok!(PriceOracle::remove_oracle(Origin::root(), oracle));
+		assert_eq!(PriceOracle::oracles(), vec![other]);
+		assert_noop!(PriceOracle::remove_oracle(Origin::root(), oracle), Error::<Test>::NotOracle);
+	});
+}
+
+#[test]
+fn trading_pairs_are_managed_by_pair_origin() {
+	new_test_ext().execute_with(|| {
+		let who = sr25519::Public::from_raw([1; 32]);
+
+		assert_noop!(PriceOracle::register_pair(Origin::signed(who), 4), DispatchError::BadOrigin);
+		assert_ok!(PriceOracle::register_pair(Origin::root(), 4));
+		assert!(PriceOracle::is_trading_pair(4));
+		assert_noop!(PriceOracle::register_pair(Origin::root(), 4), Error::<Test>::AlreadyRegistered);
+
+		assert_noop!(PriceOracle::deregister_pair(Origin::signed(who), 4), DispatchError::BadOrigin);
+		assert_ok!(PriceOracle::deregister_pair(Origin::root(), 4));
+		assert!(!PriceOracle::is_trading_pair(4));
+		assert_noop!(PriceOracle::deregister_pair(Origin::root(), 4), Error::<Test>::NotRegistered);
+	});
+}
+
+#[test]
+fn trading_pairs_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PriceOracle::pair_count(), 4);
+		for pair in 4 .. MaxPairs::get() {
+			assert_ok!(PriceOracle::register_pair(Origin::root(), pair));
+		}
+		assert_eq!(PriceOracle::pair_count(), MaxPairs::get());
+		assert_noop!(PriceOracle::register_pair(Origin::root(), 100), Error::<Test>::TooManyPairs);
+
+		assert_ok!(PriceOracle::deregister_pair(Origin::root(), 0));
+		assert_ok!(PriceOracle::register_pair(Origin::root(), 100));
+	});
+}
+
+#[test]
+fn fills_are_kept_until_the_next_block() {
+	new_test_ext().execute_with(|| {
+		PriceOracle::note_fill(fill(0, 10, 1));
+		PriceOracle::note_fill(fill(1, 20, 2));
+		PriceOracle::on_finalize(1);
+		// Offchain workers of block 1 read them from storage.
+		assert_eq!(BlockFills::get(), vec![fill(0, 10, 1), fill(1, 20, 2)]);
+		assert_eq!(PriceOracle::vwap(1), vec![(0, 10), (1, 20)]);
+
+		PriceOracle::on_initialize(2);
+		assert!(BlockFills::get().is_empty());
+	});
+}
+
+#[test]
+fn genesis_sorts_oracles_registers_pairs_and_records_the_storage_version() {
+	let oracle = sr25519::Public::from_raw([1; 32]);
+	let other = sr25519::Public::from_raw([2; 32]);
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> { oracles: vec![other, oracle, other], trading_pairs: vec![5, 5] }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		assert_eq!(PriceOracle::oracles(), vec![oracle, other]);
+		assert!(PriceOracle::is_trading_pair(5));
+		assert!(!PriceOracle::is_trading_pair(0));
+		assert_eq!(PriceOracle::pair_count(), 1);
+		assert_eq!(PriceOracle::storage_version(), Releases::CURRENT);
+	});
+}
+
+#[test]
+fn migrate_keeps_the_current_version() {
+	new_test_ext().execute_with(|| {
+		migrations::migrate::<Test>();
+
+		assert_eq!(PriceOracle::storage_version(), Releases::CURRENT);
+		assert_eq!(PriceOracle::pair_count(), 4);
+	});
+}
@@ -0,0 +1,57 @@
+//! Benchmarking setup for pallet-template
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+
+/// Fill `Heap` with `h` values, in descending order so that they already form a max-heap.
+fn set_heap(h: u32) {
+	Heap::put((0 .. h).rev().collect::<Vec<_>>());
+}
+
+benchmarks! {
+	_ { }
+
+	do_something {
+		// Worst case: the heap is one value short of full, and the new value is the largest, so it
+		// is sifted all the way up to the root.
+		let h in 0 .. T::MaxHeapLen::get() - 1;
+		set_heap(h);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), u32::max_value())
+	verify {
+		assert_eq!(Something::get(), Some(u32::max_value()));
+		assert_eq!(Heap::get().len() as u32, h + 1);
+		assert_eq!(Heap::get()[0], u32::max_value());
+	}
+
+	cause_error {
+		// The heap is not touched, which this checks up to a full heap.
+		let h in 0 .. T::MaxHeapLen::get();
+		set_heap(h);
+		// Worst case: a value is present, so it is read, incremented and written back.
+		Something::put(1);
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Something::get(), Some(2));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_do_something::<Test>());
+			assert_ok!(test_benchmark_cause_error::<Test>());
+		});
+	}
+}
@@ -0,0 +1,22 @@
+//! Default weights for pallet-template, used by runtimes that configure `WeightInfo = ()`.
+//!
+//! Runtimes should prefer the weights generated against their own configuration with the
+//! node's `benchmark` subcommand.
+
+#![allow(unused_parens)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight as DbWeight}};
+
+impl crate::WeightInfo for () {
+	fn do_something(h: u32) -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add((60_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn cause_error(_h: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
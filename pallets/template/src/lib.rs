@@ -4,9 +4,13 @@
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// https://substrate.dev/docs/en/knowledgebase/runtime/frame
 
-use frame_support::{decl_module, decl_storage, decl_event, decl_error, dispatch, traits::Get};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, traits::Get, weights::Weight,
+};
 use frame_system::ensure_signed;
-use binary_heap_plus::{BinaryHeap, MaxComparator};
+use codec::{Encode, Decode};
+use binary_heap_plus::{BinaryHeap, DeterministicCompare, MaxComparator};
+use sp_std::vec::Vec;
 
 #[cfg(test)]
 mod mock;
@@ -14,10 +18,45 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod benchmarking;
+mod default_weights;
+pub mod migrations;
+
+/// Weight functions needed for this pallet.
+pub trait WeightInfo {
+	fn do_something(h: u32) -> Weight;
+	fn cause_error(h: u32) -> Weight;
+}
+
+/// Storage layouts of this pallet. A new variant is added with every storage migration.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum Releases {
+	/// Storage as it was before versioning was introduced.
+	V1_0_0,
+	/// `StorageVersion` is recorded.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+impl Releases {
+	/// The layout this version of the pallet reads and writes.
+	pub const CURRENT: Releases = Releases::V2_0_0;
+}
+
 /// Configure the pallet by specifying the parameters and types on which it depends.
 pub trait Trait: frame_system::Trait {
 	/// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+	/// Maximum number of values kept in `Heap`.
+	type MaxHeapLen: Get<u32>;
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
 }
 
 // The pallet's runtime storage items.
@@ -30,6 +69,12 @@ decl_storage! {
 		// Learn more about declaring storage items:
 		// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
 		Something get(fn something): Option<u32>;
+		/// Every value stored with `do_something`, as a max-heap.
+		Heap get(fn heap): Vec<u32>;
+
+		/// Storage layout the on-chain state is in. Chains that predate versioning read the
+		/// default, `V1_0_0`, while new chains start in the current layout.
+		StorageVersion get(fn storage_version) build(|_| Releases::CURRENT): Releases;
 	}
 }
 
@@ -50,6 +95,8 @@ decl_error! {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// `Heap` already holds `MaxHeapLen` values.
+		HeapFull,
 	}
 }
 
@@ -66,19 +113,21 @@ decl_module! {
 
 		/// An example dispatchable that takes a singles value as a parameter, writes the value to
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[weight = 10_000 + T::DbWeight::get().writes(1)]
+		///
+		/// The value is also pushed onto `Heap`, so its weight depends on the size of the heap.
+		#[weight = T::WeightInfo::do_something(Heap::decode_len().unwrap_or(0) as u32)]
 		pub fn do_something(origin, something: u32) -> dispatch::DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
 			let who = ensure_signed(origin)?;
 
-			let mut h = test_heap();
-			h.push(something);
-			h.push(1);
-			h.push(13000);
-			assert_eq!(h.peek().unwrap(),&(13000 as u32), "We are testing the working of heap {},{}",h.peek().unwrap(),&13000);
+			let mut heap = BinaryHeap::from_vec_deterministic(Heap::get(), MaxComparator);
+			ensure!(heap.len() < T::MaxHeapLen::get() as usize, Error::<T>::HeapFull);
+			heap.push(something);
+
 			// Update storage.
+			Heap::put(heap.into_vec());
 			Something::put(something);
 
 			// Emit an event.
@@ -88,7 +137,7 @@ decl_module! {
 		}
 
 		/// An example dispatchable that may throw a custom error.
-		#[weight = 10_000 + T::DbWeight::get().reads_writes(1,1)]
+		#[weight = T::WeightInfo::cause_error(Heap::decode_len().unwrap_or(0) as u32)]
 		pub fn cause_error(origin) -> dispatch::DispatchResult {
 			let _who = ensure_signed(origin)?;
 
@@ -108,10 +157,10 @@ decl_module! {
 	}
 }
 
-
-pub fn test_heap() -> BinaryHeap<u32, MaxComparator> {
-	use binary_heap_plus::*;
-	use sp_std::vec;
-	let h: BinaryHeap<u32> = BinaryHeap::from_vec(vec![]);
-	h
+/// Creates an empty heap for use in runtime logic.
+///
+/// Only comparators implementing `DeterministicCompare` are accepted, so that every
+/// validator orders the heap in exactly the same way.
+pub fn new_heap<T, C: DeterministicCompare<T> + Default>() -> BinaryHeap<T, C> {
+	BinaryHeap::from_vec_deterministic(Vec::new(), C::default())
 }
\ No newline at end of file
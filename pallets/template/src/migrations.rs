@@ -0,0 +1,46 @@
+//! Storage migrations of the template pallet.
+//!
+//! Every migration moves `StorageVersion` one release forward and does nothing when the state is
+//! already past it, so running `migrate` on every runtime upgrade is safe.
+
+use frame_support::{storage::StorageValue, traits::Get, weights::Weight};
+use crate::{Trait, Releases, StorageVersion};
+
+/// Run all pending migrations and return the weight they consumed.
+pub fn migrate<T: Trait>() -> Weight {
+	let mut weight = T::DbWeight::get().reads(1);
+
+	if StorageVersion::get() == Releases::V1_0_0 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+	}
+
+	weight
+}
+
+/// Check that the state can be migrated. Run against live state before an upgrade is proposed.
+#[cfg(feature = "try-runtime")]
+pub fn pre_migrate<T: Trait>() -> Result<(), &'static str> {
+	if StorageVersion::get() > Releases::CURRENT {
+		return Err("Template storage is newer than this runtime");
+	}
+	Ok(())
+}
+
+/// Check that the state is in the layout this runtime expects after `migrate`.
+#[cfg(feature = "try-runtime")]
+pub fn post_migrate<T: Trait>() -> Result<(), &'static str> {
+	if StorageVersion::get() != Releases::CURRENT {
+		return Err("Template storage was not migrated to the current version");
+	}
+	Ok(())
+}
+
+/// Introduce `StorageVersion`. No other item changed layout.
+mod v2 {
+	use super::*;
+
+	pub fn migrate<T: Trait>() -> Weight {
+		StorageVersion::put(Releases::V2_0_0);
+		T::DbWeight::get().writes(1)
+	}
+}
@@ -1,5 +1,6 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, Heap, Releases, Something, migrations, mock::*};
+use frame_support::storage::StorageValue;
+use frame_support::{assert_ok, assert_noop, traits::Get, weights::constants::RocksDbWeight};
 
 #[test]
 fn it_works_for_default_value() {
@@ -11,6 +12,19 @@ fn it_works_for_default_value() {
 	});
 }
 
+#[test]
+fn values_are_kept_in_a_bounded_max_heap() {
+	new_test_ext().execute_with(|| {
+		for value in &[5, 9, 7] {
+			assert_ok!(TemplateModule::do_something(Origin::signed(1), *value));
+		}
+		assert_eq!(Heap::get().len(), 3);
+		assert_eq!(Heap::get()[0], 9);
+
+		assert_noop!(TemplateModule::do_something(Origin::signed(1), 1), Error::<Test>::HeapFull);
+	});
+}
+
 #[test]
 fn correct_error_for_none_value() {
 	new_test_ext().execute_with(|| {
@@ -21,3 +35,38 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn migrate_records_storage_version() {
+	new_test_ext().execute_with(|| {
+		Something::put(7);
+		assert_eq!(TemplateModule::storage_version(), Releases::V1_0_0);
+
+		migrations::migrate::<Test>();
+
+		assert_eq!(TemplateModule::storage_version(), Releases::CURRENT);
+		assert_eq!(TemplateModule::something(), Some(7));
+	});
+}
+
+#[test]
+fn genesis_starts_at_current_version() {
+	let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::default().assimilate_storage::<Test>(&mut storage).unwrap();
+	sp_io::TestExternalities::from(storage).execute_with(|| {
+		assert_eq!(TemplateModule::storage_version(), Releases::CURRENT);
+
+		// Only the version is read; no migration runs.
+		assert_eq!(migrations::migrate::<Test>(), RocksDbWeight::get().reads(1));
+	});
+}
+
+#[test]
+fn migrate_is_idempotent() {
+	new_test_ext().execute_with(|| {
+		migrations::migrate::<Test>();
+		migrations::migrate::<Test>();
+
+		assert_eq!(TemplateModule::storage_version(), Releases::CURRENT);
+	});
+}
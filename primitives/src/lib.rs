@@ -0,0 +1,6 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Primitives shared by the runtime and its pallets, so that none of them owns what the others
+//! depend on.
+
+pub mod oracle;
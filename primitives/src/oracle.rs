@@ -0,0 +1,34 @@
+//! Keys of the price oracles.
+
+use sp_core::crypto::KeyTypeId;
+
+/// Key type of the accounts offchain workers sign price reports with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"orcl");
+
+/// Application crypto of the keys of offchain workers, so that they cannot be mixed up with the
+/// keys of other roles of the node.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{app_crypto::{app_crypto, sr25519}, MultiSignature, MultiSigner};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signs the transactions and payloads of offchain workers.
+	pub struct OracleId;
+
+	/// For runtimes using `MultiSignature`.
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	/// For runtimes using plain sr25519 signatures, such as test runtimes.
+	impl frame_system::offchain::AppCrypto<sp_core::sr25519::Public, sp_core::sr25519::Signature>
+		for OracleId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
@@ -0,0 +1,11 @@
+//! Weights of the pallets this runtime shares with the BABE runtime, so that
+//! `scripts/benchmark.sh` keeps both up to date.
+
+#[path = "../../runtime/src/weights/frame_system.rs"]
+pub mod frame_system;
+#[path = "../../runtime/src/weights/pallet_balances.rs"]
+pub mod pallet_balances;
+#[path = "../../runtime/src/weights/pallet_template.rs"]
+pub mod pallet_template;
+#[path = "../../runtime/src/weights/pallet_timestamp.rs"]
+pub mod pallet_timestamp;
@@ -0,0 +1,45 @@
+//! Addresses that extrinsics refer to accounts with.
+
+use codec::{Decode, DecodeAll, Encode};
+use sp_runtime::traits::{LookupError, StaticLookup};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// An account, given by its id, by the short index claimed for it in the indices pallet, or by
+/// the raw bytes of its id.
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub enum MultiAddress<AccountId, AccountIndex> {
+	/// The id of the account.
+	Id(AccountId),
+	/// The index of the account.
+	Index(#[codec(compact)] AccountIndex),
+	/// The encoded id of the account, for clients that do not know the type of account ids.
+	Raw(Vec<u8>),
+}
+
+impl<AccountId, AccountIndex> From<AccountId> for MultiAddress<AccountId, AccountIndex> {
+	fn from(id: AccountId) -> Self {
+		MultiAddress::Id(id)
+	}
+}
+
+/// Looks up the account of a [`MultiAddress`], resolving indices with the indices pallet.
+pub struct MultiAddressLookup<T>(PhantomData<T>);
+
+impl<T: pallet_indices::Trait> StaticLookup for MultiAddressLookup<T> {
+	type Source = MultiAddress<T::AccountId, T::AccountIndex>;
+	type Target = T::AccountId;
+
+	fn lookup(address: Self::Source) -> Result<Self::Target, LookupError> {
+		match address {
+			MultiAddress::Id(id) => Ok(id),
+			MultiAddress::Index(index) =>
+				pallet_indices::Module::<T>::lookup_index(index).ok_or(LookupError),
+			MultiAddress::Raw(raw) => T::AccountId::decode_all(&raw).map_err(|_| LookupError),
+		}
+	}
+
+	fn unlookup(id: Self::Target) -> Self::Source {
+		MultiAddress::Id(id)
+	}
+}
@@ -0,0 +1,49 @@
+//! Storage migrations run when the runtime is upgraded.
+
+use frame_support::{traits::OnRuntimeUpgrade, weights::Weight};
+use sp_runtime::RuntimeString;
+use crate::Runtime;
+
+/// Runs the storage migrations of our pallets, in order.
+///
+/// `Executive` calls this before the `on_runtime_upgrade` hooks of the pallets, and only when
+/// `spec_version` changed. Every migration checks the storage version of its pallet, so it's a
+/// no-op once enacted. Add the migrations of a pallet here when it gets its first one.
+pub struct Migrations;
+
+impl OnRuntimeUpgrade for Migrations {
+	fn on_runtime_upgrade() -> Weight {
+		template::migrations::migrate::<Runtime>()
+			.saturating_add(price_oracle::migrations::migrate::<Runtime>())
+	}
+}
+
+#[cfg(feature = "try-runtime")]
+impl Migrations {
+	/// Checks run on the state before migrating.
+	pub fn pre_migrate() -> Result<(), &'static str> {
+		template::migrations::pre_migrate::<Runtime>()?;
+		price_oracle::migrations::pre_migrate::<Runtime>()?;
+		Ok(())
+	}
+
+	/// Checks run on the state after migrating.
+	pub fn post_migrate() -> Result<(), &'static str> {
+		template::migrations::post_migrate::<Runtime>()?;
+		price_oracle::migrations::post_migrate::<Runtime>()?;
+		Ok(())
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Dry-runs a runtime upgrade on the state of the block it's called at.
+	///
+	/// Only implemented by runtimes built with the `try-runtime` feature. Never call this on a
+	/// runtime that is live on chain.
+	pub trait TryRuntime {
+		/// Run every migration and pallet upgrade hook between the pre and post checks.
+		///
+		/// Returns the weight consumed and the maximum block weight, or the first failed check.
+		fn on_runtime_upgrade() -> Result<(Weight, Weight), RuntimeString>;
+	}
+}
@@ -0,0 +1,44 @@
+//! Weights for frame_system
+//!
+//! Copied from the weights of the Substrate node runtime at v2.0.0-rc6, not benchmarked against
+//! this runtime. Replace them with the output of `scripts/benchmark.sh`, run on reference hardware.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Trait> frame_system::WeightInfo for WeightInfo<T> {
+	fn remark(_b: u32, ) -> Weight {
+		(1_973_000 as Weight)
+	}
+	fn set_heap_pages() -> Weight {
+		(2_816_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_changes_trie_config() -> Weight {
+		(11_539_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_storage(i: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((833_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn kill_storage(i: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((597_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn kill_prefix(p: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((857_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn suicide() -> Weight {
+		(37_209_000 as Weight)
+	}
+}
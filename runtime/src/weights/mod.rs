@@ -0,0 +1,19 @@
+//! Weights for the pallets of this runtime.
+//!
+//! Each file says where its weights come from. `scripts/benchmark.sh` regenerates all of them with
+//! the node's `benchmark` subcommand.
+
+pub mod frame_system;
+pub mod pallet_balances;
+pub mod pallet_collective;
+pub mod pallet_democracy;
+pub mod pallet_elections_phragmen;
+pub mod pallet_im_online;
+pub mod pallet_indices;
+pub mod pallet_price_oracle;
+pub mod pallet_scheduler;
+pub mod pallet_session;
+pub mod pallet_staking;
+pub mod pallet_template;
+pub mod pallet_timestamp;
+pub mod pallet_treasury;
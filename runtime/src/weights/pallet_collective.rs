@@ -0,0 +1,80 @@
+//! Weights for pallet_collective
+//!
+//! Copied from the weights of the Substrate node runtime at v2.0.0-rc6, not benchmarked against
+//! this runtime. Replace them with the output of `scripts/benchmark.sh`, run on reference hardware.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Trait> pallet_collective::WeightInfo for WeightInfo<T> {
+	fn set_members(m: u32, n: u32, p: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((20_933_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((102_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((28_605_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(p as Weight)))
+	}
+	fn execute(b: u32, m: u32, ) -> Weight {
+		(23_425_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((120_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	fn propose_execute(b: u32, m: u32, ) -> Weight {
+		(28_482_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((240_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+	fn propose_proposed(b: u32, m: u32, p: u32, ) -> Weight {
+		(46_860_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((138_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((397_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn vote(m: u32, ) -> Weight {
+		(38_469_000 as Weight)
+			.saturating_add((276_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn close_early_disapproved(m: u32, p: u32, ) -> Weight {
+		(51_464_000 as Weight)
+			.saturating_add((244_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((355_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn close_early_approved(b: u32, m: u32, p: u32, ) -> Weight {
+		(72_788_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((247_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((358_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn close_disapproved(m: u32, p: u32, ) -> Weight {
+		(57_084_000 as Weight)
+			.saturating_add((248_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((360_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn close_approved(b: u32, m: u32, p: u32, ) -> Weight {
+		(78_394_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add((250_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((356_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
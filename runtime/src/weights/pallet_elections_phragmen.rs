@@ -0,0 +1,76 @@
+//! Weights for pallet_elections_phragmen
+//!
+//! Copied from the weights of the Substrate node runtime at v2.0.0-rc6, not benchmarked against
+//! this runtime. Replace them with the output of `scripts/benchmark.sh`, run on reference hardware.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Trait> pallet_elections_phragmen::WeightInfo for WeightInfo<T> {
+	fn vote(v: u32, ) -> Weight {
+		(89_627_000 as Weight)
+			.saturating_add((197_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_update(v: u32, ) -> Weight {
+		(54_724_000 as Weight)
+			.saturating_add((213_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_voter() -> Weight {
+		(71_961_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn report_defunct_voter_correct(c: u32, v: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((1_746_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((34_067_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn report_defunct_voter_incorrect(c: u32, v: u32, ) -> Weight {
+		(0 as Weight)
+			.saturating_add((1_733_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add((34_103_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn submit_candidacy(c: u32, ) -> Weight {
+		(70_971_000 as Weight)
+			.saturating_add((420_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn renounce_candidacy_candidate(c: u32, ) -> Weight {
+		(43_844_000 as Weight)
+			.saturating_add((206_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn renounce_candidacy_members() -> Weight {
+		(76_082_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn renounce_candidacy_runners_up() -> Weight {
+		(46_662_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_member_with_replacement() -> Weight {
+		(116_514_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn remove_member_wrong_refund() -> Weight {
+		(9_816_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+}
@@ -0,0 +1,21 @@
+//! Weights for pallet_im_online
+//!
+//! Copied from the weights of the Substrate node runtime at v2.0.0-rc6, not benchmarked against
+//! this runtime. Replace them with the output of `scripts/benchmark.sh`, run on reference hardware.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Trait> pallet_im_online::WeightInfo for WeightInfo<T> {
+	fn validate_unsigned_and_then_heartbeat(k: u32, e: u32, ) -> Weight {
+		(114_379_000 as Weight)
+			.saturating_add((219_000 as Weight).saturating_mul(k as Weight))
+			.saturating_add((481_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
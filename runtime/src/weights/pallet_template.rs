@@ -0,0 +1,25 @@
+//! Weights for pallet_template
+//!
+//! Estimates written by hand, not benchmarked. Replace them with the output of
+//! `scripts/benchmark.sh`, run on reference hardware, before they are relied on.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Trait> template::WeightInfo for WeightInfo<T> {
+	fn do_something(h: u32) -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add((60_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn cause_error(_h: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
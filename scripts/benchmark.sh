@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+
+set -e
+
+echo "*** Regenerating runtime weights"
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+# Runtime pallet name and the weight module it is written to.
+PALLETS=(
+    "frame_system:frame_system"
+    "pallet_balances:pallet_balances"
+    "pallet_collective:pallet_collective"
+    "pallet_democracy:pallet_democracy"
+    "pallet_elections_phragmen:pallet_elections_phragmen"
+    "pallet_im_online:pallet_im_online"
+    "pallet_indices:pallet_indices"
+    "pallet_scheduler:pallet_scheduler"
+    "pallet_session:pallet_session"
+    "pallet_staking:pallet_staking"
+    "pallet_timestamp:pallet_timestamp"
+    "pallet_treasury:pallet_treasury"
+    "price_oracle:pallet_price_oracle"
+    "template:pallet_template"
+)
+
+cargo build --release --features runtime-benchmarks
+
+for entry in "${PALLETS[@]}"; do
+    pallet=${entry%%:*}
+    module=${entry##*:}
+
+    echo "*** Benchmarking $pallet"
+    ./target/release/node-template benchmark \
+        --chain dev \
+        --execution wasm \
+        --wasm-execution compiled \
+        --pallet "$pallet" \
+        --extrinsic '*' \
+        --steps 50 \
+        --repeat 20 \
+        --output "runtime/src/weights/$module.rs"
+done
@@ -1 +1 @@
-./target/debug/node-template  --alice --port 30334 --ws-port 9000
+./target/debug/node-template --chain=local --alice --port 30334 --ws-port 9000
\ No newline at end of file
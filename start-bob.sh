@@ -1 +1 @@
-./target/debug/node-template --bob --port 30334 --ws-port 9000 --bootnodes /ip4/15.207.157.197/tcp/30334/p2p/12D3KooWD11a4S79hZJqJ3ky7omQqQgtA8uRP3PXF2B1vhgj3zsB
+./target/debug/node-template --chain=local --bob --port 30334 --ws-port 9000
\ No newline at end of file